@@ -0,0 +1,78 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use qtum_address_rust::hashes::{sha256, sha256d};
+use qtum_address_rust::{QtumAddress, QtumNetwork};
+
+const BATCH: usize = 1_000;
+
+const HEX: &str = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+const BASE58: &str = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+
+fn hex_inputs() -> Vec<String> {
+    (0..BATCH as u32)
+        .map(|i| format!("{:08x}ca2ae7c00b248bb2832d6f480f27da68", i))
+        .collect()
+}
+
+fn single(c: &mut Criterion) {
+    let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+    let mut group = c.benchmark_group("single");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("gethexaddress", |b| {
+        b.iter(|| qtum.gethexaddress(black_box(BASE58)))
+    });
+    group.bench_function("fromhexaddress", |b| {
+        b.iter(|| qtum.fromhexaddress(black_box(HEX)))
+    });
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    let qtum = QtumAddress::new(QtumNetwork::Testnet);
+    let hex = hex_inputs();
+    let base58: Vec<String> = qtum
+        .batch_fromhexaddress(&hex)
+        .into_iter()
+        .map(|address| address.unwrap().into())
+        .collect();
+
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("fromhexaddress x1000", |b| {
+        b.iter(|| qtum.batch_fromhexaddress(black_box(&hex)))
+    });
+    group.bench_function("gethexaddress x1000", |b| {
+        b.iter(|| qtum.batch_gethexaddress(black_box(&base58)))
+    });
+    group.finish();
+}
+
+/// Checksum the old way: two separate SHA256 rounds, each result copied into a `Vec`
+fn checksum_with_vecs(payload: &[u8]) -> Vec<u8> {
+    let hash = |bytes: &[u8]| sha256(bytes).to_vec();
+    hash(&hash(payload))[0..4].to_vec()
+}
+
+/// Checksum the way the crate computes it: the first 4 bytes of its own `sha256d`
+fn checksum_direct(payload: &[u8]) -> [u8; 4] {
+    let hash = sha256d(payload);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn checksum(c: &mut Criterion) {
+    let mut payload = vec![0x78];
+    payload.extend_from_slice(&hex::decode(HEX).unwrap());
+    assert_eq!(checksum_with_vecs(&payload), checksum_direct(&payload));
+
+    let mut group = c.benchmark_group("checksum");
+    group.bench_function("vec copies", |b| {
+        b.iter(|| checksum_with_vecs(black_box(&payload)))
+    });
+    group.bench_function("direct bytes", |b| {
+        b.iter(|| checksum_direct(black_box(&payload)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, single, batch, checksum);
+criterion_main!(benches);
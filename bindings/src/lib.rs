@@ -0,0 +1,6 @@
+//! C and WebAssembly builds of `qtum-address-rust`
+//!
+//! The library crate itself is an rlib only, so that it also builds without `std`. This crate
+//! links it into a `cdylib` and a `staticlib`: the `ffi` feature (default) exports the C
+//! interface declared in `include/qtum_address.h`, the `wasm` feature the wasm-bindgen API.
+pub use qtum_address_rust::*;
@@ -0,0 +1,191 @@
+//! `qtum-address`: converting and checking Qtum addresses from the shell
+
+use clap::{Parser, Subcommand};
+use qtum_address_rust::{detect_networks, QtumAddress, QtumAddressError, QtumNetwork};
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+/// Converts and checks Qtum addresses.
+///
+/// Without an address argument, one address per line is read from stdin.
+#[derive(Parser)]
+#[command(name = "qtum-address", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Converts a base58 pubkey hash address to its hex form
+    ToHex {
+        /// mainnet, testnet, regtest or custom(0xNN)
+        #[arg(long, short, default_value = "mainnet")]
+        network: QtumNetwork,
+        address: Option<String>,
+    },
+    /// Converts a hex address to its base58 pubkey hash form
+    FromHex {
+        /// mainnet, testnet, regtest or custom(0xNN)
+        #[arg(long, short, default_value = "mainnet")]
+        network: QtumNetwork,
+        hex: Option<String>,
+    },
+    /// Checks a base58 address, against the given network if any
+    Validate {
+        /// Only accept addresses of this network
+        #[arg(long, short)]
+        network: Option<QtumNetwork>,
+        address: Option<String>,
+    },
+    /// Prints the networks a base58 address can belong to
+    DetectNetwork { address: Option<String> },
+}
+
+impl Command {
+    /// The address argument, `None` to read from stdin
+    fn input(&self) -> Option<&str> {
+        match self {
+            Command::ToHex { address, .. }
+            | Command::Validate { address, .. }
+            | Command::DetectNetwork { address } => address.as_deref(),
+            Command::FromHex { hex, .. } => hex.as_deref(),
+        }
+    }
+
+    /// Output line for one input
+    fn run(&self, input: &str) -> Result<String, QtumAddressError> {
+        match self {
+            Command::ToHex { network, .. } => QtumAddress::new(*network)
+                .gethexaddress(input)
+                .map(String::from),
+            Command::FromHex { network, .. } => QtumAddress::new(*network)
+                .fromhexaddress(input)
+                .map(String::from),
+            Command::Validate {
+                network: Some(network),
+                ..
+            } => QtumAddress::new(*network)
+                .validate_address(input)
+                .map(|()| format!("valid {} address", network)),
+            Command::Validate { network: None, .. } => {
+                detect_networks(input).map(|networks| format!("valid {} address", names(networks)))
+            }
+            Command::DetectNetwork { .. } => detect_networks(input).map(names),
+        }
+    }
+}
+
+/// Network names separated by ` or `, testnet and regtest addresses look the same
+fn names(networks: &[QtumNetwork]) -> String {
+    networks
+        .iter()
+        .map(|network| network.name())
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let inputs: Vec<String> = match cli.command.input() {
+        Some(input) => vec![input.to_string()],
+        None => match io::stdin().lock().lines().collect() {
+            Ok(lines) => lines,
+            Err(err) => {
+                eprintln!("error: could not read stdin: {}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    let mut failed = false;
+    for input in inputs
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+    {
+        match cli.command.run(input) {
+            Ok(output) => println!("{}", output),
+            Err(err) => {
+                eprintln!("error: {}: {}", input, err);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[&str], input: &str) -> Result<String, QtumAddressError> {
+        let cli = Cli::try_parse_from(std::iter::once("qtum-address").chain(args.iter().copied()))
+            .unwrap();
+        cli.command.run(input)
+    }
+
+    #[test]
+    fn subcommands() {
+        assert_eq!(
+            run(
+                &["to-hex", "--network", "testnet"],
+                "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+            )
+            .unwrap(),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+        assert_eq!(
+            run(
+                &["from-hex", "-n", "testnet"],
+                "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+            )
+            .unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+        assert_eq!(
+            run(&["validate"], "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap(),
+            "valid testnet or regtest address"
+        );
+        assert_eq!(
+            run(
+                &["validate", "--network", "testnet"],
+                "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+            )
+            .unwrap(),
+            "valid testnet address"
+        );
+        assert_eq!(
+            run(&["detect-network"], "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap(),
+            "testnet or regtest"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            run(&["to-hex"], "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::NetworkMismatch { .. })
+        ));
+        assert_eq!(
+            run(&["validate"], "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert!(Cli::try_parse_from(["qtum-address", "to-hex", "--network", "moon"]).is_err());
+    }
+
+    #[test]
+    fn address_argument_is_optional() {
+        let cli = Cli::try_parse_from(["qtum-address", "from-hex"]).unwrap();
+        assert_eq!(cli.command.input(), None);
+
+        let cli = Cli::try_parse_from(["qtum-address", "detect-network", "abc"]).unwrap();
+        assert_eq!(cli.command.input(), Some("abc"));
+    }
+}
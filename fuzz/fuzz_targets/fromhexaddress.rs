@@ -0,0 +1,17 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use qtum_address_rust::*;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(address) = std::str::from_utf8(data) {
+        for network in [QtumNetwork::Mainnet, QtumNetwork::Testnet].iter() {
+            let qtum = QtumAddress::new(*network);
+
+            // Every accepted hex address converts back to its normalized form
+            if let Ok(base58) = qtum.fromhexaddress(address) {
+                let hex = qtum.gethexaddress(&base58).unwrap();
+                assert_eq!(hex, normalize_hex_address(address).unwrap());
+            }
+        }
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use qtum_address_rust::*;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(address) = std::str::from_utf8(data) {
+        for network in [QtumNetwork::Mainnet, QtumNetwork::Testnet].iter() {
+            let _ = QtumAddress::new(*network).gethexaddress(address);
+            let _ = QtumAddress::new(*network).gethexaddress_unchecked(address);
+        }
+    }
+});
@@ -0,0 +1,263 @@
+#ifndef QTUM_ADDRESS_H
+#define QTUM_ADDRESS_H
+
+/* Generated with cbindgen, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Version byte of mainnet pubkey hash addresses
+ */
+#define MAINNET_PREFIX 58
+
+/**
+ * Version byte of testnet (and regtest) pubkey hash addresses
+ */
+#define TESTNET_PREFIX 120
+
+/**
+ * Version byte of regtest pubkey hash addresses, the same as testnet's
+ */
+#define REGTEST_PREFIX TESTNET_PREFIX
+
+/**
+ * Length of the hash160 carried by an address
+ */
+#define ADDRESS_PAYLOAD_LEN 20
+
+/**
+ * Length of a hex address without the `0x` prefix
+ */
+#define HEX_ADDRESS_LEN (2 * ADDRESS_PAYLOAD_LEN)
+
+/**
+ * Length of the base58check checksum
+ */
+#define CHECKSUM_LEN 4
+
+/**
+ * Maximum length of a base58 address.
+ *
+ * 25 bytes (version, 20-byte hash, checksum) never encode to more than 35 base58 characters,
+ * longer inputs are rejected before decoding.
+ */
+#define MAX_BASE58_ADDRESS_LEN 35
+
+/**
+ * Maximum length of the input of `decode_base58check` and `decode_base58check_prefixed`.
+ *
+ * Base58 decoding is quadratic in the input length, so longer inputs are rejected before
+ * decoding. 128 characters leave room for 111-character BIP32 extended keys.
+ */
+#define MAX_BASE58CHECK_LEN 128
+
+/**
+ * Maximum length of a hex address: 40 hex characters plus the optional `0x` prefix
+ */
+#define MAX_HEX_ADDRESS_LEN (HEX_ADDRESS_LEN + 2)
+
+/**
+ * Size of an ABI word
+ */
+#define ABI_WORD_LEN 32
+
+/**
+ * Success
+ */
+#define QTUM_OK 0
+
+/**
+ * `QtumAddressError::EmptyInput`
+ */
+#define QTUM_ERR_EMPTY_INPUT -1
+
+/**
+ * `QtumAddressError::InvalidBase58`
+ */
+#define QTUM_ERR_INVALID_BASE58 -2
+
+/**
+ * `QtumAddressError::InvalidCharacter`
+ */
+#define QTUM_ERR_INVALID_CHARACTER -3
+
+/**
+ * `QtumAddressError::Whitespace`
+ */
+#define QTUM_ERR_WHITESPACE -4
+
+/**
+ * `QtumAddressError::Homoglyph`
+ */
+#define QTUM_ERR_HOMOGLYPH -5
+
+/**
+ * `QtumAddressError::InvalidHex`
+ */
+#define QTUM_ERR_INVALID_HEX -6
+
+/**
+ * `QtumAddressError::InvalidLength`
+ */
+#define QTUM_ERR_INVALID_LENGTH -7
+
+/**
+ * `QtumAddressError::TooLong`
+ */
+#define QTUM_ERR_TOO_LONG -8
+
+/**
+ * `QtumAddressError::InvalidChecksum`
+ */
+#define QTUM_ERR_INVALID_CHECKSUM -9
+
+/**
+ * `QtumAddressError::NetworkMismatch`
+ */
+#define QTUM_ERR_NETWORK_MISMATCH -10
+
+/**
+ * `QtumAddressError::UnknownNetwork`
+ */
+#define QTUM_ERR_UNKNOWN_NETWORK -11
+
+/**
+ * `QtumAddressError::UnrecognizedFormat`
+ */
+#define QTUM_ERR_UNRECOGNIZED_FORMAT -12
+
+/**
+ * `QtumAddressError::WrongAddressType`
+ */
+#define QTUM_ERR_WRONG_ADDRESS_TYPE -13
+
+/**
+ * `QtumAddressError::InvalidPublicKey`
+ */
+#define QTUM_ERR_INVALID_PUBLIC_KEY -14
+
+/**
+ * `QtumAddressError::ScriptTooLong`
+ */
+#define QTUM_ERR_SCRIPT_TOO_LONG -15
+
+/**
+ * `QtumAddressError::InvalidMultisig`
+ */
+#define QTUM_ERR_INVALID_MULTISIG -16
+
+/**
+ * `QtumAddressError::InvalidPrivateKey`
+ */
+#define QTUM_ERR_INVALID_PRIVATE_KEY -17
+
+/**
+ * `QtumAddressError::InvalidBech32`
+ */
+#define QTUM_ERR_INVALID_BECH32 -18
+
+/**
+ * `QtumAddressError::HrpMismatch`
+ */
+#define QTUM_ERR_HRP_MISMATCH -19
+
+/**
+ * `QtumAddressError::InvalidWitnessProgram`
+ */
+#define QTUM_ERR_INVALID_WITNESS_PROGRAM -20
+
+/**
+ * `QtumAddressError::UnrepresentableWitnessProgram`
+ */
+#define QTUM_ERR_UNREPRESENTABLE_WITNESS_PROGRAM -21
+
+/**
+ * `QtumAddressError::InvalidAbiPadding`
+ */
+#define QTUM_ERR_INVALID_ABI_PADDING -22
+
+/**
+ * `QtumAddressError::InvalidUri`
+ */
+#define QTUM_ERR_INVALID_URI -23
+
+/**
+ * `QtumAddressError::InvalidAmount`
+ */
+#define QTUM_ERR_INVALID_AMOUNT -24
+
+/**
+ * `QtumAddressError::InvalidTransaction`
+ */
+#define QTUM_ERR_INVALID_TRANSACTION -25
+
+/**
+ * `QtumAddressError::InvalidScript`
+ */
+#define QTUM_ERR_INVALID_SCRIPT -26
+
+/**
+ * `address` or `out` is a null pointer
+ */
+#define QTUM_ERR_NULL_POINTER -100
+
+/**
+ * `address` is not valid UTF-8
+ */
+#define QTUM_ERR_INVALID_UTF8 -101
+
+/**
+ * `out_len` is too small for the result and its NUL terminator
+ */
+#define QTUM_ERR_BUFFER_TOO_SMALL -102
+
+/**
+ * Largest redeem script a script hash output can be spent with
+ */
+#define MAX_REDEEM_SCRIPT_LEN 520
+
+/**
+ * Longest bech32 string allowed by BIP173
+ */
+#define MAX_BECH32_LEN 90
+
+/**
+ * Highest witness version
+ */
+#define MAX_WITNESS_VERSION 16
+
+/**
+ * Length of a version 0 pay to witness script hash program
+ */
+#define WSH_PROGRAM_LEN 32
+
+/**
+ * Default number of characters kept on each side by `short`
+ */
+#define SHORT_DEFAULT_LEN 6
+
+/**
+ * Converts a base58 pubkeyhash address to a hex address.
+ *
+ * # Safety
+ *
+ * `address` must be a valid NUL-terminated string and `out` must point to at least `out_len`
+ * writable bytes.
+ */
+int qtum_gethexaddress(uint8_t prefix, const char *address, char *out, size_t out_len);
+
+/**
+ * Converts a raw hex address to a base58 pubkeyhash address.
+ *
+ * # Safety
+ *
+ * `address` must be a valid NUL-terminated string and `out` must point to at least `out_len`
+ * writable bytes.
+ */
+int qtum_fromhexaddress(uint8_t prefix, const char *address, char *out, size_t out_len);
+
+#endif  /* QTUM_ADDRESS_H */
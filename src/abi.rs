@@ -0,0 +1,78 @@
+//! The `address` type of the contract ABI, a hex address left padded to a 32-byte word
+
+use crate::{decode_hex, HexAddress, QtumAddressError, ADDRESS_PAYLOAD_LEN};
+
+/// Size of an ABI word
+pub const ABI_WORD_LEN: usize = 32;
+
+/// Zero bytes in front of the address in its ABI word
+const PADDING_LEN: usize = ABI_WORD_LEN - ADDRESS_PAYLOAD_LEN;
+
+/// Encoding a hex address as an ABI `address` argument: 12 zero bytes followed by the address.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let word = abi_encode_address("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68").unwrap();
+/// assert_eq!(word[..12], [0; 12]);
+/// assert_eq!(abi_decode_address(&word).unwrap(), "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+/// ```
+pub fn abi_encode_address(hex_address: &str) -> Result<[u8; ABI_WORD_LEN], QtumAddressError> {
+    let address = decode_hex(hex_address)?;
+
+    let mut word = [0u8; ABI_WORD_LEN];
+    word[PADDING_LEN..].copy_from_slice(&address);
+    Ok(word)
+}
+
+/// Decoding an ABI `address` word, the 12 leading bytes must be zero
+pub fn abi_decode_address(word: &[u8; ABI_WORD_LEN]) -> Result<HexAddress, QtumAddressError> {
+    if let Some(position) = word[..PADDING_LEN].iter().position(|b| *b != 0) {
+        return Err(QtumAddressError::InvalidAbiPadding { position });
+    }
+
+    let mut address = [0u8; ADDRESS_PAYLOAD_LEN];
+    address.copy_from_slice(&word[PADDING_LEN..]);
+    Ok(HexAddress::from(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn calldata_word() {
+        // Argument of transfer(address,uint256) as produced by solc
+        let word = hex::decode("0000000000000000000000006c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap();
+
+        let encoded = abi_encode_address("6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68").unwrap();
+        assert_eq!(encoded[..], word[..]);
+        assert_eq!(
+            abi_decode_address(&encoded).unwrap(),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+    }
+
+    #[test]
+    fn dirty_padding_is_rejected() {
+        let mut word = abi_encode_address("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68").unwrap();
+        word[11] = 0x01;
+
+        assert_eq!(
+            abi_decode_address(&word),
+            Err(QtumAddressError::InvalidAbiPadding { position: 11 })
+        );
+    }
+
+    #[test]
+    fn base58_input_is_rejected() {
+        assert_eq!(
+            abi_encode_address("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 34
+            })
+        );
+    }
+}
@@ -0,0 +1,569 @@
+use crate::convert::detect_input_kind;
+use crate::{
+    checksum, decode_address_bytes, decode_hex, encode_base58check, Base58Address, HexAddress,
+    HexFormat, InputKind, QtumAddress, QtumAddressError, QtumNetwork, ADDRESS_PAYLOAD_LEN,
+    RAW_ADDRESS_LEN, VERSIONED_PAYLOAD_LEN,
+};
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+/// Kind of a Qtum address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum AddressType {
+    /// Pay to public key hash
+    P2PKH,
+    /// Pay to script hash
+    P2SH,
+    /// Contract address, only found in hex form.
+    ///
+    /// Contracts have no base58 form of their own, `Address` renders them with the pubkey hash
+    /// byte like the `fromhexaddress` RPC of qtumd does.
+    Contract,
+}
+
+/// Network and address kind of a known version byte
+pub(crate) const fn classify_version(version: u8) -> Option<(QtumNetwork, AddressType)> {
+    const MAINNET_PUBKEY: u8 = QtumNetwork::Mainnet.to_prefix_byte();
+    const MAINNET_SCRIPT: u8 = QtumNetwork::Mainnet.to_script_prefix_byte();
+    const TESTNET_PUBKEY: u8 = QtumNetwork::Testnet.to_prefix_byte();
+    const TESTNET_SCRIPT: u8 = QtumNetwork::Testnet.to_script_prefix_byte();
+
+    match version {
+        MAINNET_PUBKEY => Some((QtumNetwork::Mainnet, AddressType::P2PKH)),
+        MAINNET_SCRIPT => Some((QtumNetwork::Mainnet, AddressType::P2SH)),
+        TESTNET_PUBKEY => Some((QtumNetwork::Testnet, AddressType::P2PKH)),
+        TESTNET_SCRIPT => Some((QtumNetwork::Testnet, AddressType::P2SH)),
+        _ => None,
+    }
+}
+
+/// Detects the kind of a base58 address from its version byte, the checksum is verified
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let kind = detect_address_type("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(kind, AddressType::P2PKH);
+/// ```
+pub fn detect_address_type(address: &str) -> Result<AddressType, QtumAddressError> {
+    let payload = decode_address_bytes(address, true)?;
+
+    classify_version(payload[0])
+        .map(|(_, kind)| kind)
+        .ok_or(QtumAddressError::UnknownNetwork { prefix: payload[0] })
+}
+
+/// Classifies a hex address as a contract or a pubkey hash.
+///
+/// A hex address carries no type information, so this is a heuristic: precompiled and system
+/// contracts live at small addresses (all but the last two bytes zero) and are reported as
+/// `Contract`, everything else as `P2PKH`. Contracts created by transactions cannot be told
+/// apart from pubkey hashes without querying the chain.
+pub fn classify_hex_address(hex: &str) -> Result<AddressType, QtumAddressError> {
+    let bytes = decode_hex(hex)?;
+
+    if bytes[..18].iter().all(|&b| b == 0) {
+        Ok(AddressType::Contract)
+    } else {
+        Ok(AddressType::P2PKH)
+    }
+}
+
+/// Validated Qtum address: network, kind and the 20-byte hash160
+///
+/// Equality, ordering and hashing compare network, kind and hash160 in that order. An
+/// address parsed from hex equals the one parsed from its base58 form on mainnet and
+/// testnet. Base58 parsing reports regtest addresses as `Testnet`, which shares their prefix
+/// bytes, and rejects those of `Custom` networks.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let addr: Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+/// let hex = Address::from_hex("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68", QtumNetwork::Testnet).unwrap();
+///
+/// assert_eq!(addr, hex);
+/// assert_eq!(addr.to_hex(), "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address {
+    network: QtumNetwork,
+    kind: AddressType,
+    hash160: [u8; ADDRESS_PAYLOAD_LEN],
+}
+
+impl Address {
+    /// Creating an address from its parts
+    pub const fn new(
+        network: QtumNetwork,
+        kind: AddressType,
+        hash160: [u8; ADDRESS_PAYLOAD_LEN],
+    ) -> Self {
+        Self {
+            network,
+            kind,
+            hash160,
+        }
+    }
+
+    /// Parsing a base58check address, the network is taken from the prefix byte.
+    ///
+    /// Only prefixes of known networks are accepted, addresses of `Custom` networks are
+    /// rejected with `UnknownNetwork`.
+    pub fn from_base58(address: &str) -> Result<Self, QtumAddressError> {
+        let payload = decode_address_bytes(address, true)?;
+
+        Self::from_payload(&payload)
+    }
+
+    /// Decoding the compact binary form produced by `to_compact_bytes` or
+    /// `to_compact_bytes_with_checksum`.
+    ///
+    /// 21-byte input is the version byte and the hash160, 25-byte input additionally carries
+    /// the base58check checksum, which is verified. The version byte must belong to a known network.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self, QtumAddressError> {
+        match bytes.len() {
+            VERSIONED_PAYLOAD_LEN => Self::from_payload(bytes),
+            RAW_ADDRESS_LEN => {
+                if checksum(&bytes[..VERSIONED_PAYLOAD_LEN])[..] != bytes[VERSIONED_PAYLOAD_LEN..] {
+                    return Err(QtumAddressError::InvalidChecksum);
+                }
+                Self::from_payload(&bytes[..VERSIONED_PAYLOAD_LEN])
+            }
+            found => Err(QtumAddressError::InvalidLength {
+                expected: if found < VERSIONED_PAYLOAD_LEN {
+                    VERSIONED_PAYLOAD_LEN
+                } else {
+                    RAW_ADDRESS_LEN
+                },
+                found,
+            }),
+        }
+    }
+
+    /// Building an address from the version byte and the hash160
+    fn from_payload(payload: &[u8]) -> Result<Self, QtumAddressError> {
+        let (network, kind) = classify_version(payload[0])
+            .ok_or(QtumAddressError::UnknownNetwork { prefix: payload[0] })?;
+
+        let mut hash160 = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash160.copy_from_slice(&payload[1..VERSIONED_PAYLOAD_LEN]);
+
+        Ok(Self::new(network, kind, hash160))
+    }
+
+    /// Parsing a hex address as a pubkey hash, hex addresses carry no network information
+    pub fn from_hex(address: &str, network: QtumNetwork) -> Result<Self, QtumAddressError> {
+        let hash160 = decode_hex(address)?;
+
+        Ok(Self::new(network, AddressType::P2PKH, hash160))
+    }
+
+    /// Network of the address
+    pub fn network(&self) -> QtumNetwork {
+        self.network
+    }
+
+    /// Same hash and kind on another network, e.g. the testnet twin of a mainnet address
+    pub const fn with_network(self, network: QtumNetwork) -> Self {
+        Self { network, ..self }
+    }
+
+    /// Whether the address belongs to the Qtum mainnet
+    pub fn is_mainnet(&self) -> bool {
+        self.network.is_mainnet()
+    }
+
+    /// Whether the address belongs to the Qtum testnet
+    pub fn is_testnet(&self) -> bool {
+        self.network.is_testnet()
+    }
+
+    /// Kind of the address
+    pub fn kind(&self) -> AddressType {
+        self.kind
+    }
+
+    /// Raw 20-byte hash of the address
+    pub fn hash160(&self) -> [u8; ADDRESS_PAYLOAD_LEN] {
+        self.hash160
+    }
+
+    /// Base58check rendering of the address
+    pub fn to_base58(&self) -> Base58Address {
+        Base58Address(encode_base58check(self.version(), &self.hash160))
+    }
+
+    /// Version byte of the network and kind
+    fn version(&self) -> u8 {
+        match self.kind {
+            AddressType::P2PKH | AddressType::Contract => self.network.to_prefix_byte(),
+            AddressType::P2SH => self.network.to_script_prefix_byte(),
+        }
+    }
+
+    /// Hex rendering of the address for use in smart contracts
+    pub fn to_hex(&self) -> HexAddress {
+        HexAddress::from(self.hash160)
+    }
+
+    /// Shortened base58 form for display, e.g. `qTTH1Y…8pyrBt`
+    pub fn short(&self) -> String {
+        self.to_base58().short()
+    }
+
+    /// Shortened `0x` hex form for display, e.g. `0x6c89a1…27da68`
+    pub fn short_hex(&self) -> String {
+        self.to_hex_with(HexFormat::new().prefix(true)).short()
+    }
+
+    /// Hex rendering of the address in the given format
+    pub fn to_hex_with(&self, format: HexFormat) -> HexAddress {
+        format.format(&self.hash160)
+    }
+
+    /// Compact binary form for storage: the version byte followed by the hash160
+    pub fn to_compact_bytes(&self) -> [u8; VERSIONED_PAYLOAD_LEN] {
+        let mut bytes = [0u8; VERSIONED_PAYLOAD_LEN];
+        bytes[0] = self.version();
+        bytes[1..].copy_from_slice(&self.hash160);
+
+        bytes
+    }
+
+    /// Compact binary form followed by the 4-byte base58check checksum
+    pub fn to_compact_bytes_with_checksum(&self) -> [u8; RAW_ADDRESS_LEN] {
+        let compact = self.to_compact_bytes();
+
+        let mut bytes = [0u8; RAW_ADDRESS_LEN];
+        bytes[..VERSIONED_PAYLOAD_LEN].copy_from_slice(&compact);
+        bytes[VERSIONED_PAYLOAD_LEN..].copy_from_slice(&checksum(&compact));
+
+        bytes
+    }
+}
+
+impl QtumAddress {
+    /// Checks whether two addresses in hex or base58 form are the same address.
+    ///
+    /// Hex addresses are taken to belong to the configured network and kind, case and the
+    /// `0x` prefix are ignored. Base58 addresses of other known networks are never equal
+    /// to addresses of the configured one.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::new(QtumNetwork::Testnet);
+    /// let same = qtum.is_same_address(
+    ///     "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+    ///     "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68",
+    /// );
+    ///
+    /// assert_eq!(same, Ok(true));
+    /// ```
+    pub fn is_same_address(&self, a: &str, b: &str) -> Result<bool, QtumAddressError> {
+        Ok(self.parse_address(a)? == self.parse_address(b)?)
+    }
+
+    /// Parsing an address in either form into an `Address`
+    pub(crate) fn parse_address(&self, input: &str) -> Result<Address, QtumAddressError> {
+        match detect_input_kind(input)? {
+            InputKind::Hex => Ok(Address::new(
+                self.network,
+                self.kind,
+                self.decode_hex(input)?,
+            )),
+            InputKind::Base58 => {
+                let payload = decode_address_bytes(input, self.verify_checksum)?;
+
+                let (network, kind) = match self.kind_of_version(payload[0]) {
+                    Some(kind) => (self.network, kind),
+                    None => classify_version(payload[0])
+                        .ok_or(QtumAddressError::UnknownNetwork { prefix: payload[0] })?,
+                };
+
+                let mut hash160 = [0u8; ADDRESS_PAYLOAD_LEN];
+                hash160.copy_from_slice(&payload[1..VERSIONED_PAYLOAD_LEN]);
+
+                Ok(Address::new(network, kind, hash160))
+            }
+        }
+    }
+}
+
+impl FromStr for Address {
+    type Err = QtumAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::from_base58(s)
+    }
+}
+
+impl fmt::Display for Address {
+    /// Base58 form, shortened with `{:#}`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_base58(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QtumAddress;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use alloc::{format, vec};
+
+    const BASE58: &str = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+    const HEX: &str = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+    #[test]
+    fn parse_and_render() {
+        let addr = Address::from_base58(BASE58).unwrap();
+
+        assert_eq!(addr.network(), QtumNetwork::Testnet);
+        assert_eq!(addr.kind(), AddressType::P2PKH);
+        assert_eq!(hex::encode(addr.hash160()), HEX);
+        assert_eq!(addr.to_base58(), BASE58);
+        assert_eq!(addr.to_hex(), HEX);
+        assert_eq!(addr.to_string(), BASE58);
+    }
+
+    #[test]
+    fn equal_across_representations() {
+        let from_base58: Address = BASE58.parse().unwrap();
+        let from_hex = Address::from_hex(HEX, QtumNetwork::Testnet).unwrap();
+        let other_network = Address::from_hex(HEX, QtumNetwork::Mainnet).unwrap();
+
+        assert_eq!(from_base58, from_hex);
+        assert_ne!(from_base58, other_network);
+    }
+
+    #[test]
+    fn versions_follow_network_params() {
+        for network in QtumNetwork::all() {
+            // Regtest shares testnet's version bytes
+            let named = match network {
+                QtumNetwork::Regtest => QtumNetwork::Testnet,
+                network => *network,
+            };
+            let params = network.params().unwrap();
+
+            assert_eq!(
+                classify_version(params.pubkey_address),
+                Some((named, AddressType::P2PKH))
+            );
+            assert_eq!(
+                classify_version(params.script_address),
+                Some((named, AddressType::P2SH))
+            );
+        }
+        assert_eq!(classify_version(0x00), None);
+    }
+
+    #[test]
+    fn networks_lost_in_base58() {
+        // Regtest shares testnet's prefix, its base58 form parses back as testnet
+        let regtest = Address::from_hex(HEX, QtumNetwork::Regtest).unwrap();
+        let reparsed = Address::from_base58(&regtest.to_base58()).unwrap();
+        assert_ne!(reparsed, regtest);
+        assert_eq!(reparsed, regtest.with_network(QtumNetwork::Testnet));
+
+        let custom = Address::from_hex(HEX, QtumNetwork::Custom(0x55)).unwrap();
+        assert_eq!(
+            Address::from_base58(&custom.to_base58()),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x55 })
+        );
+    }
+
+    #[test]
+    fn validation_errors() {
+        assert_eq!(
+            Address::from_base58("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            Address::from_hex("0xhello", QtumNetwork::Testnet),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 5
+            })
+        );
+
+        let unknown = encode_base58check(0x00, &[0x11; 20]);
+        assert_eq!(
+            Address::from_base58(&unknown),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x00 })
+        );
+    }
+
+    #[test]
+    fn compact_bytes_round_trip() {
+        let addr = Address::from_base58(BASE58).unwrap();
+
+        let compact = addr.to_compact_bytes();
+        assert_eq!(compact[0], 0x78);
+        assert_eq!(hex::encode(&compact[1..]), HEX);
+        assert_eq!(Address::from_compact_bytes(&compact), Ok(addr));
+
+        let with_checksum = addr.to_compact_bytes_with_checksum();
+        assert_eq!(with_checksum[..21], compact[..]);
+        assert_eq!(Address::from_compact_bytes(&with_checksum), Ok(addr));
+
+        let mainnet = Address::from_hex(HEX, QtumNetwork::Mainnet).unwrap();
+        assert_eq!(
+            Address::from_compact_bytes(&mainnet.to_compact_bytes_with_checksum()),
+            Ok(mainnet)
+        );
+    }
+
+    #[test]
+    fn compact_bytes_errors() {
+        let addr = Address::from_base58(BASE58).unwrap();
+        let with_checksum = addr.to_compact_bytes_with_checksum();
+
+        assert_eq!(
+            Address::from_compact_bytes(&with_checksum[..20]),
+            Err(QtumAddressError::InvalidLength {
+                expected: 21,
+                found: 20
+            })
+        );
+        assert_eq!(
+            Address::from_compact_bytes(&with_checksum[..24]),
+            Err(QtumAddressError::InvalidLength {
+                expected: 25,
+                found: 24
+            })
+        );
+
+        let mut corrupted = with_checksum;
+        corrupted[5] ^= 0x01;
+        assert_eq!(
+            Address::from_compact_bytes(&corrupted),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+
+        let mut unknown = addr.to_compact_bytes();
+        unknown[0] = 0x00;
+        assert_eq!(
+            Address::from_compact_bytes(&unknown),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x00 })
+        );
+    }
+
+    #[test]
+    fn script_hash_address() {
+        let script = QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+            .fromhexaddress(HEX)
+            .unwrap();
+
+        let addr = Address::from_base58(&script).unwrap();
+        assert_eq!(addr.network(), QtumNetwork::Mainnet);
+        assert_eq!(addr.kind(), AddressType::P2SH);
+        assert_eq!(addr.to_base58(), script);
+        assert_eq!(addr.to_compact_bytes()[0], 0x32);
+        assert_eq!(
+            Address::from_compact_bytes(&addr.to_compact_bytes()),
+            Ok(addr)
+        );
+    }
+
+    #[test]
+    fn detect_base58_kind() {
+        let script = QtumAddress::new_p2sh(QtumNetwork::Testnet)
+            .fromhexaddress(HEX)
+            .unwrap();
+
+        assert_eq!(detect_address_type(BASE58), Ok(AddressType::P2PKH));
+        assert_eq!(detect_address_type(&script), Ok(AddressType::P2SH));
+        assert_eq!(
+            detect_address_type(&encode_base58check(0x00, &[0x11; 20])),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x00 })
+        );
+    }
+
+    #[test]
+    fn classify_hex_kind() {
+        assert_eq!(classify_hex_address(HEX), Ok(AddressType::P2PKH));
+        assert_eq!(
+            classify_hex_address("0x0000000000000000000000000000000000000001"),
+            Ok(AddressType::Contract)
+        );
+        assert_eq!(
+            classify_hex_address("0000000000000000000000000000000000000085"),
+            Ok(AddressType::Contract)
+        );
+        assert_eq!(
+            classify_hex_address("0000000000000000000000000000000000010000"),
+            Ok(AddressType::P2PKH)
+        );
+        assert_eq!(
+            classify_hex_address("0xhello"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 5
+            })
+        );
+    }
+
+    #[test]
+    fn same_address_across_formats() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(qtum.is_same_address(BASE58, HEX), Ok(true));
+        assert_eq!(qtum.is_same_address(HEX, BASE58), Ok(true));
+        assert_eq!(
+            qtum.is_same_address(&format!("0x{}", HEX.to_uppercase()), HEX),
+            Ok(true)
+        );
+        assert_eq!(
+            qtum.is_same_address(BASE58, "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG"),
+            Ok(false)
+        );
+
+        let mainnet = QtumAddress::new(QtumNetwork::Mainnet)
+            .fromhexaddress(HEX)
+            .unwrap();
+        assert_eq!(qtum.is_same_address(&mainnet, BASE58), Ok(false));
+        assert_eq!(qtum.is_same_address(&mainnet, HEX), Ok(false));
+
+        assert_eq!(
+            qtum.is_same_address(BASE58, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn addresses_as_keys() {
+        use std::collections::{BTreeSet, HashMap};
+
+        let testnet = Address::from_hex(HEX, QtumNetwork::Testnet).unwrap();
+        let mainnet = Address::from_hex(HEX, QtumNetwork::Mainnet).unwrap();
+
+        let mut balances = HashMap::new();
+        balances.insert(Address::from_base58(BASE58).unwrap(), 10);
+        assert_eq!(balances.get(&testnet), Some(&10));
+        assert_eq!(balances.get(&mainnet), None);
+
+        let low = Address::new(QtumNetwork::Testnet, AddressType::P2PKH, [0x00; 20]);
+        let sorted: Vec<Address> = vec![testnet, mainnet, low, testnet]
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(sorted, vec![mainnet, low, testnet]);
+    }
+
+    #[test]
+    fn short_forms() {
+        let addr = Address::from_base58(BASE58).unwrap();
+
+        assert_eq!(addr.short(), "qTTH1Y\u{2026}8pyrBt");
+        assert_eq!(format!("{:#}", addr), "qTTH1Y\u{2026}8pyrBt");
+        assert_eq!(format!("{}", addr), BASE58);
+        assert_eq!(addr.short_hex(), "0x6c89a1\u{2026}27da68");
+    }
+}
@@ -0,0 +1,148 @@
+//! Base58 with the bitcoin alphabet, only needing `alloc`
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Bitcoin base58 alphabet, indexed by digit value
+pub(crate) const BITCOIN: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Digit value of each ASCII character, `0xff` for characters outside the alphabet
+const DIGITS: [u8; 128] = {
+    let mut digits = [0xffu8; 128];
+    let mut i = 0;
+    while i < BITCOIN.len() {
+        digits[BITCOIN[i] as usize] = i as u8;
+        i += 1;
+    }
+    digits
+};
+
+/// Encoding bytes as base58, every leading zero byte becomes a leading `1`
+pub(crate) fn encode(input: &[u8]) -> String {
+    let zeros = input.iter().take_while(|b| **b == 0).count();
+
+    // Little endian base58 digits of the input without its leading zeros
+    let mut digits: Vec<u8> = Vec::with_capacity((input.len() - zeros) * 138 / 100 + 1);
+    for byte in &input[zeros..] {
+        let mut carry = u32::from(*byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut encoded = String::with_capacity(zeros + digits.len());
+    encoded.extend(core::iter::repeat(BITCOIN[0] as char).take(zeros));
+    encoded.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|digit| BITCOIN[*digit as usize] as char),
+    );
+    encoded
+}
+
+/// Decoding base58, every leading `1` becomes a leading zero byte.
+///
+/// `None` for characters outside the alphabet.
+pub(crate) fn decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.as_bytes();
+    let zeros = input.iter().take_while(|c| **c == BITCOIN[0]).count();
+
+    // Little endian bytes of the input without its leading ones
+    let mut bytes: Vec<u8> = Vec::with_capacity((input.len() - zeros) * 733 / 1000 + 1);
+    for c in &input[zeros..] {
+        let mut carry = match DIGITS.get(*c as usize) {
+            Some(digit) if *digit != 0xff => u32::from(*digit),
+            _ => return None,
+        };
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut decoded = Vec::with_capacity(zeros + bytes.len());
+    decoded.resize(zeros, 0);
+    decoded.extend(bytes.iter().rev());
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn known_vectors() {
+        for (bytes, text) in &[
+            (&[][..], ""),
+            (&[0x00][..], "1"),
+            (&[0x00, 0x00][..], "11"),
+            (&[0x61][..], "2g"),
+            (&[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd][..], "11233QC4"),
+            (&[28, 215, 33, 155][..], "jkuzA"),
+        ] {
+            assert_eq!(encode(bytes), *text);
+            assert_eq!(decode(text).unwrap(), *bytes);
+        }
+    }
+
+    #[test]
+    fn random_round_trip_keeps_leading_zeros() {
+        let mut next = crate::tests::xorshift(0xa076_1d64_78bd_642f);
+
+        for _ in 0..2000 {
+            let zeros = (next() % 5) as usize;
+            let len = (next() % 40) as usize;
+            let mut bytes = vec![0u8; zeros];
+            bytes.extend((0..len).map(|_| next() as u8));
+
+            let encoded = encode(&bytes);
+            let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+            let leading_ones = encoded.bytes().take_while(|c| *c == b'1').count();
+
+            assert_eq!(leading_ones, leading_zeros, "{:02x?}", bytes);
+            assert_eq!(decode(&encoded).unwrap(), bytes, "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn version_zero_addresses() {
+        use crate::*;
+
+        // Bitcoin genesis block address and the all-zero hash, both with version byte 0
+        let genesis = hex::decode("62e907b15cbf27d5425399ebf6f0fb50ebb88f18").unwrap();
+        for (hash, address) in &[
+            (&genesis[..], "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+            (&[0u8; 20][..], "1111111111111111111114oLvT2"),
+        ] {
+            assert_eq!(encode_base58check(0x00, hash), *address);
+            assert_eq!(decode_base58check(address), Ok((0x00, hash.to_vec())));
+
+            let custom = QtumAddress::new(QtumNetwork::Custom(0x00));
+            assert_eq!(custom.fromhexaddress_bytes(hash).unwrap(), *address);
+            assert_eq!(
+                hex::decode(custom.gethexaddress(address).unwrap().as_str()).unwrap(),
+                *hash
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_characters_outside_the_alphabet() {
+        for input in &["0", "O", "I", "l", "1 1", "é"] {
+            assert_eq!(decode(input), None);
+        }
+    }
+}
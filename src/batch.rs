@@ -0,0 +1,106 @@
+use crate::{Base58Address, HexAddress, QtumAddress, QtumAddressError};
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+impl QtumAddress {
+    /// Converts many base58 addresses to hex addresses, results are in input order
+    pub fn batch_gethexaddress<S: AsRef<str>>(
+        &self,
+        addresses: &[S],
+    ) -> Vec<Result<HexAddress, QtumAddressError>> {
+        addresses
+            .iter()
+            .map(|address| self.gethexaddress(address.as_ref()))
+            .collect()
+    }
+
+    /// Converts many hex addresses to base58 addresses, results are in input order
+    pub fn batch_fromhexaddress<S: AsRef<str>>(
+        &self,
+        addresses: &[S],
+    ) -> Vec<Result<Base58Address, QtumAddressError>> {
+        addresses
+            .iter()
+            .map(|address| self.fromhexaddress(address.as_ref()))
+            .collect()
+    }
+
+    /// Parallel `batch_gethexaddress` on the rayon thread pool, enabled with the `rayon` feature
+    #[cfg(feature = "rayon")]
+    pub fn par_batch_gethexaddress<S: AsRef<str> + Sync>(
+        &self,
+        addresses: &[S],
+    ) -> Vec<Result<HexAddress, QtumAddressError>> {
+        addresses
+            .par_iter()
+            .map(|address| self.gethexaddress(address.as_ref()))
+            .collect()
+    }
+
+    /// Parallel `batch_fromhexaddress` on the rayon thread pool, enabled with the `rayon` feature
+    #[cfg(feature = "rayon")]
+    pub fn par_batch_fromhexaddress<S: AsRef<str> + Sync>(
+        &self,
+        addresses: &[S],
+    ) -> Vec<Result<Base58Address, QtumAddressError>> {
+        addresses
+            .par_iter()
+            .map(|address| self.fromhexaddress(address.as_ref()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const BASE58: [&str; 3] = [
+        "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+        "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu",
+        "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG",
+    ];
+    const HEX: [&str; 3] = [
+        "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+        "0xhello",
+        "49a80104c0d27a9ba29678d07e87a57151107613",
+    ];
+
+    #[test]
+    fn batch_matches_single_conversions() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let hex = qtum.batch_gethexaddress(&BASE58);
+        assert_eq!(hex.len(), 3);
+        for (result, address) in hex.iter().zip(BASE58.iter()) {
+            assert_eq!(*result, qtum.gethexaddress(address));
+        }
+        assert_eq!(hex[1], Err(QtumAddressError::InvalidChecksum));
+
+        let owned: Vec<String> = HEX.iter().map(|s| s.to_string()).collect();
+        let base58 = qtum.batch_fromhexaddress(&owned);
+        assert_eq!(base58[0].as_ref().unwrap(), BASE58[0]);
+        assert!(base58[1].is_err());
+        assert_eq!(base58[2].as_ref().unwrap(), BASE58[2]);
+
+        assert!(qtum.batch_gethexaddress::<&str>(&[]).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_batch_matches_sequential() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let inputs: Vec<&str> = BASE58.iter().cycle().take(1000).cloned().collect();
+        assert_eq!(
+            qtum.par_batch_gethexaddress(&inputs),
+            qtum.batch_gethexaddress(&inputs)
+        );
+
+        let inputs: Vec<&str> = HEX.iter().cycle().take(1000).cloned().collect();
+        assert_eq!(
+            qtum.par_batch_fromhexaddress(&inputs),
+            qtum.batch_fromhexaddress(&inputs)
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! `qtum:` payment request URIs, as described in BIP21
+
+use crate::{QtumAddress, QtumAddressError, QtumNetwork};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// URI scheme of Qtum payment requests
+const SCHEME: &str = "qtum:";
+
+/// Decimal places of a QTUM amount, one satoshi is 10^-8 QTUM
+const AMOUNT_DECIMALS: usize = 8;
+
+/// Payment request decoded from a `qtum:` URI
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PaymentRequest {
+    /// Base58 or bech32 address to pay to
+    pub address: String,
+    /// Requested amount in QTUM
+    pub amount: Option<f64>,
+    /// Label of the recipient
+    pub label: Option<String>,
+    /// Message describing the payment
+    pub message: Option<String>,
+}
+
+/// Builds a `qtum:<address>?amount=<qtum>&label=<label>&message=<message>` URI.
+///
+/// The address must be a valid base58 or bech32 address of a known network. Amounts are
+/// written with at most 8 decimals. Negative or non-finite amounts, and those that change when
+/// rounded to whole satoshis, give `InvalidAmount`. The label and message are percent-encoded.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let uri = to_bip21_uri(
+///     "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+///     Some(1.5),
+///     Some("Coffee shop"),
+///     None,
+/// )
+/// .unwrap();
+/// assert_eq!(uri, "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?amount=1.5&label=Coffee%20shop");
+/// ```
+pub fn to_bip21_uri(
+    address: &str,
+    amount_qtum: Option<f64>,
+    label: Option<&str>,
+    message: Option<&str>,
+) -> Result<String, QtumAddressError> {
+    validate_address(address)?;
+
+    let mut params = Vec::new();
+    if let Some(amount) = amount_qtum {
+        params.push(format!("amount={}", format_amount(amount)?));
+    }
+    if let Some(label) = label {
+        params.push(format!("label={}", percent_encode(label)));
+    }
+    if let Some(message) = message {
+        params.push(format!("message={}", percent_encode(message)));
+    }
+
+    let mut uri = format!("{}{}", SCHEME, address);
+    if !params.is_empty() {
+        uri.push('?');
+        uri.push_str(&params.join("&"));
+    }
+
+    Ok(uri)
+}
+
+/// Decodes a `qtum:` URI into its payment request.
+///
+/// The scheme is case-insensitive and the address must be valid. Unknown parameters are
+/// ignored except for `req-` ones, which BIP21 requires to be unsupported, and repeated
+/// parameters give `InvalidUri`.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let request = from_bip21_uri("qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?amount=0.02&message=Order%20%2342").unwrap();
+/// assert_eq!(request.amount, Some(0.02));
+/// assert_eq!(request.message.as_deref(), Some("Order #42"));
+/// ```
+pub fn from_bip21_uri(uri: &str) -> Result<PaymentRequest, QtumAddressError> {
+    match uri.get(..SCHEME.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME) => {}
+        _ => return Err(QtumAddressError::InvalidUri),
+    }
+
+    let rest = &uri[SCHEME.len()..];
+    let (address, query) = match rest.find('?') {
+        Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+        None => (rest, None),
+    };
+    validate_address(address)?;
+
+    let mut request = PaymentRequest {
+        address: address.to_string(),
+        amount: None,
+        label: None,
+        message: None,
+    };
+
+    for param in query.into_iter().flat_map(|query| query.split('&')) {
+        let (key, value) = match param.find('=') {
+            Some(index) => (&param[..index], &param[index + 1..]),
+            None => return Err(QtumAddressError::InvalidUri),
+        };
+
+        let duplicate = match key {
+            "amount" => request.amount.replace(parse_amount(value)?).is_some(),
+            "label" => request.label.replace(percent_decode(value)?).is_some(),
+            "message" => request.message.replace(percent_decode(value)?).is_some(),
+            _ if key.starts_with("req-") => return Err(QtumAddressError::InvalidUri),
+            _ => false,
+        };
+        if duplicate {
+            return Err(QtumAddressError::InvalidUri);
+        }
+    }
+
+    Ok(request)
+}
+
+/// Checking a base58 address of any known network or a bech32 address of its network's prefix
+fn validate_address(address: &str) -> Result<(), QtumAddressError> {
+    let lower = address.to_ascii_lowercase();
+    let bech32_network = QtumNetwork::all().iter().find(|network| {
+        network
+            .bech32_hrp()
+            .is_some_and(|hrp| lower.starts_with(hrp) && lower[hrp.len()..].starts_with('1'))
+    });
+
+    match bech32_network {
+        Some(network) => QtumAddress::new(*network)
+            .decode_witness_program(address)
+            .map(|_| ()),
+        None => QtumAddress::gethexaddress_any(address).map(|_| ()),
+    }
+}
+
+/// Amount with up to 8 decimals and no trailing zeros, the written amount must parse back
+/// to `amount`
+fn format_amount(amount: f64) -> Result<String, QtumAddressError> {
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(QtumAddressError::InvalidAmount);
+    }
+
+    let formatted = format!("{:.*}", AMOUNT_DECIMALS, amount);
+    if formatted.parse::<f64>() != Ok(amount) {
+        return Err(QtumAddressError::InvalidAmount);
+    }
+    Ok(formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string())
+}
+
+/// Decimal amount without sign or exponent and with at most 8 decimals
+fn parse_amount(value: &str) -> Result<f64, QtumAddressError> {
+    let (whole, fraction) = match value.find('.') {
+        Some(index) => (&value[..index], &value[index + 1..]),
+        None => (value, ""),
+    };
+
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !digits(whole) || !digits(fraction) || fraction.len() > AMOUNT_DECIMALS {
+        return Err(QtumAddressError::InvalidAmount);
+    }
+
+    value.parse().map_err(|_| QtumAddressError::InvalidAmount)
+}
+
+/// Percent-encoding everything but the RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Decoding `%XX` escapes, the result must be UTF-8
+fn percent_decode(value: &str) -> Result<String, QtumAddressError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let escape = bytes
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| core::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(QtumAddressError::InvalidUri)?;
+            decoded.push(escape);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| QtumAddressError::InvalidUri)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const ADDRESS: &str = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+
+    #[test]
+    fn uri_round_trip() {
+        let uri = to_bip21_uri(
+            ADDRESS,
+            Some(20.3),
+            Some("Luke-Jr"),
+            Some("Donation for project xyz & co/ünicode"),
+        )
+        .unwrap();
+        assert_eq!(
+            uri,
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?amount=20.3&label=Luke-Jr&message=Donation%20for%20project%20xyz%20%26%20co%2F%C3%BCnicode"
+        );
+
+        let request = from_bip21_uri(&uri).unwrap();
+        assert_eq!(request.address, ADDRESS);
+        assert_eq!(request.amount, Some(20.3));
+        assert_eq!(request.label.as_deref(), Some("Luke-Jr"));
+        assert_eq!(
+            request.message.as_deref(),
+            Some("Donation for project xyz & co/ünicode")
+        );
+    }
+
+    #[test]
+    fn address_only() {
+        let uri = to_bip21_uri(ADDRESS, None, None, None).unwrap();
+        assert_eq!(uri, format!("qtum:{}", ADDRESS));
+        assert_eq!(
+            from_bip21_uri(&format!("QTUM:{}", ADDRESS)).unwrap(),
+            from_bip21_uri(&uri).unwrap()
+        );
+
+        let request = from_bip21_uri(&uri).unwrap();
+        assert_eq!(request.amount, None);
+        assert_eq!(request.label, None);
+        assert_eq!(request.message, None);
+    }
+
+    #[test]
+    fn amounts() {
+        for (amount, text) in &[
+            (1.0, "1"),
+            (0.00000001, "0.00000001"),
+            (50.0, "50"),
+            (0.1, "0.1"),
+        ] {
+            let uri = to_bip21_uri(ADDRESS, Some(*amount), None, None).unwrap();
+            assert_eq!(uri, format!("qtum:{}?amount={}", ADDRESS, text));
+            assert_eq!(from_bip21_uri(&uri).unwrap().amount, Some(*amount));
+        }
+
+        // Sub-satoshi precision would be rounded away
+        for amount in &[
+            -1.0,
+            f64::NAN,
+            f64::INFINITY,
+            0.000000001,
+            0.123456789,
+            0.1 + 0.2,
+        ] {
+            assert_eq!(
+                to_bip21_uri(ADDRESS, Some(*amount), None, None),
+                Err(QtumAddressError::InvalidAmount)
+            );
+        }
+
+        for amount in &["", "1e3", "-1", "0.000000001", ".5", "1,5"] {
+            assert_eq!(
+                from_bip21_uri(&format!("qtum:{}?amount={}", ADDRESS, amount)),
+                Err(QtumAddressError::InvalidAmount)
+            );
+        }
+    }
+
+    #[test]
+    fn bech32_addresses() {
+        let address = "qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at0";
+        let uri = to_bip21_uri(address, Some(1.0), None, None).unwrap();
+
+        assert_eq!(from_bip21_uri(&uri).unwrap().address, address);
+        assert_eq!(
+            to_bip21_uri(
+                "qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at2",
+                None,
+                None,
+                None
+            ),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn invalid_uris() {
+        for uri in &[
+            "bitcoin:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+            "qtum",
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?amount=1&amount=2",
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?req-somethingnew=1",
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?label",
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?label=%zz",
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?label=%ff",
+            "qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt?label=%+1",
+            "qtüm:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+        ] {
+            assert_eq!(from_bip21_uri(uri), Err(QtumAddressError::InvalidUri));
+        }
+
+        assert_eq!(
+            from_bip21_uri("qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+
+        // Unknown optional parameters are ignored
+        assert_eq!(
+            from_bip21_uri(&format!("qtum:{}?somethingelse=1", ADDRESS))
+                .unwrap()
+                .address,
+            ADDRESS
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{AddressType, HexFormat, QtumAddress, QtumNetwork};
+
+/// Builder for `QtumAddress`, see `QtumAddress::builder`
+///
+/// Unset options keep the behavior of `QtumAddress::new`: mainnet pubkey hash addresses,
+/// checksums are verified, the `0x` prefix is accepted and hex output is bare lowercase.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let qtum = QtumAddress::builder()
+///     .network(QtumNetwork::Testnet)
+///     .hex_uppercase(true)
+///     .build();
+///
+/// let hex = qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(hex, "6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68");
+/// ```
+#[derive(Debug, Clone)]
+pub struct QtumAddressBuilder {
+    network: QtumNetwork,
+    kind: AddressType,
+    strict_checksum: bool,
+    allow_0x_prefix: bool,
+    hex_format: HexFormat,
+}
+
+impl QtumAddressBuilder {
+    /// Builder with the default options
+    pub const fn new() -> Self {
+        Self {
+            network: QtumNetwork::Mainnet,
+            kind: AddressType::P2PKH,
+            strict_checksum: true,
+            allow_0x_prefix: true,
+            hex_format: HexFormat::new(),
+        }
+    }
+
+    /// Network of the converter, mainnet by default
+    pub const fn network(mut self, network: QtumNetwork) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Kind of base58 address produced and accepted, pubkey hash by default
+    pub const fn address_type(mut self, kind: AddressType) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Whether base58 conversions verify the checksum, `true` by default
+    pub const fn strict_checksum(mut self, strict: bool) -> Self {
+        self.strict_checksum = strict;
+        self
+    }
+
+    /// Whether hex input may carry the `0x` prefix, `true` by default
+    pub const fn allow_0x_prefix(mut self, allow: bool) -> Self {
+        self.allow_0x_prefix = allow;
+        self
+    }
+
+    /// Whether hex output is uppercase, `false` by default
+    pub const fn hex_uppercase(mut self, uppercase: bool) -> Self {
+        self.hex_format = self.hex_format.uppercase(uppercase);
+        self
+    }
+
+    /// Whether hex output carries the `0x` prefix, `false` by default
+    pub const fn hex_prefix(mut self, prefix: bool) -> Self {
+        self.hex_format = self.hex_format.prefix(prefix);
+        self
+    }
+
+    /// Building the converter
+    pub const fn build(&self) -> QtumAddress {
+        let prefix = match self.kind {
+            AddressType::P2SH => self.network.to_script_prefix_byte(),
+            AddressType::P2PKH | AddressType::Contract => self.network.to_prefix_byte(),
+        };
+
+        QtumAddress {
+            network: self.network,
+            kind: self.kind,
+            prefix,
+            verify_checksum: self.strict_checksum,
+            allow_0x_prefix: self.allow_0x_prefix,
+            hex_format: self.hex_format,
+        }
+    }
+}
+
+impl Default for QtumAddressBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn defaults_match_new() {
+        let built = QtumAddress::builder().build();
+        let plain = QtumAddress::new(QtumNetwork::Mainnet);
+
+        let hex = "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        let addr = plain.fromhexaddress(hex).unwrap();
+
+        assert_eq!(built.fromhexaddress(hex).unwrap(), addr);
+        assert_eq!(built.gethexaddress(&addr), plain.gethexaddress(&addr));
+        assert_eq!(
+            built.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            plain.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+        );
+    }
+
+    #[test]
+    fn strict_and_lenient_disagree() {
+        let template = QtumAddress::builder().network(QtumNetwork::Testnet);
+        let strict = template.clone().allow_0x_prefix(false).build();
+        let lenient = template.strict_checksum(false).build();
+
+        let bad_checksum = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu";
+        assert_eq!(
+            strict.gethexaddress(bad_checksum),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            lenient.gethexaddress(bad_checksum).unwrap(),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+
+        let prefixed = "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        assert_eq!(
+            strict.fromhexaddress(prefixed),
+            Err(QtumAddressError::InvalidHex)
+        );
+        assert_eq!(
+            lenient.fromhexaddress(prefixed).unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+        assert_eq!(
+            strict.fromhexaddress(&prefixed[2..]).unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+    }
+
+    #[test]
+    fn output_and_kind_options() {
+        let qtum = QtumAddress::builder()
+            .network(QtumNetwork::Testnet)
+            .address_type(AddressType::P2SH)
+            .hex_prefix(true)
+            .build();
+
+        let addr = qtum
+            .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap();
+        assert_eq!(detect_address_type(&addr), Ok(AddressType::P2SH));
+        assert_eq!(
+            qtum.gethexaddress(&addr).unwrap(),
+            "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Deterministic contract addresses of the Qtum EVM, enabled with the `contract` feature
+
+use crate::{
+    decode_hex, hash160, Base58Address, HexAddress, QtumAddress, QtumAddressError,
+    ADDRESS_PAYLOAD_LEN,
+};
+use alloc::vec::Vec;
+use tiny_keccak::{Hasher, Keccak};
+
+/// RLP prefix of a string of 0 to 55 bytes
+const RLP_STRING: u8 = 0x80;
+
+/// RLP prefix of a list whose payload is 0 to 55 bytes
+const RLP_LIST: u8 = 0xc0;
+
+/// Bytes of a transaction id
+const TXID_LEN: usize = 32;
+
+/// First byte of the `CREATE2` preimage, which can never start an RLP encoded `CREATE` preimage
+const CREATE2_PREFIX: u8 = 0xff;
+
+/// Address of the contract a sender creates with the `CREATE` opcode:
+/// the last 20 bytes of keccak256 of `RLP([sender, nonce])`, as in Ethereum.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let addr = contract_address_create("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0", 0).unwrap();
+/// assert_eq!(addr, "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d");
+/// ```
+pub fn contract_address_create(
+    sender_hex: &str,
+    nonce: u64,
+) -> Result<HexAddress, QtumAddressError> {
+    let sender = decode_hex(sender_hex)?;
+
+    let mut payload = Vec::with_capacity(1 + ADDRESS_PAYLOAD_LEN + 9);
+    rlp_append_bytes(&mut payload, &sender);
+    let nonce = nonce.to_be_bytes();
+    let first = nonce.iter().position(|b| *b != 0).unwrap_or(nonce.len());
+    rlp_append_bytes(&mut payload, &nonce[first..]);
+
+    let mut rlp = Vec::with_capacity(1 + payload.len());
+    // Sender and nonce are at most 30 bytes, always a short list
+    rlp.push(RLP_LIST + payload.len() as u8);
+    rlp.extend_from_slice(&payload);
+
+    Ok(HexAddress::from(last_20_bytes(&keccak256(&rlp))))
+}
+
+/// Address of the contract a sender creates with the `CREATE2` opcode:
+/// the last 20 bytes of `keccak256(0xff || sender || salt || keccak256(init_code))`, as
+/// specified in EIP-1014.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let addr = contract_address_create2("0x0000000000000000000000000000000000000000", &[0; 32], &[0x00]).unwrap();
+/// assert_eq!(addr, "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38");
+/// ```
+pub fn contract_address_create2(
+    sender_hex: &str,
+    salt: &[u8; 32],
+    init_code: &[u8],
+) -> Result<HexAddress, QtumAddressError> {
+    let sender = decode_hex(sender_hex)?;
+
+    let mut preimage = Vec::with_capacity(1 + ADDRESS_PAYLOAD_LEN + 64);
+    preimage.push(CREATE2_PREFIX);
+    preimage.extend_from_slice(&sender);
+    preimage.extend_from_slice(salt);
+    preimage.extend_from_slice(&keccak256(init_code));
+
+    Ok(HexAddress::from(last_20_bytes(&keccak256(&preimage))))
+}
+
+/// Address of a contract deployed by a Qtum transaction output:
+/// `hash160(txid || vout)`, with the txid in its serialized little endian byte order and the
+/// output index as a little endian `u32`.
+///
+/// The txid is given as usual, 64 hex characters in display byte order.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// // First output of the coinbase of Qtum mainnet block 4995
+/// let addr = contract_address_from_outpoint(
+///     "54bc1f2a67b3c77c8035adce8f825dea3db3c17b8fc10b7c7a382aa1ccaa01f2",
+///     0,
+/// )
+/// .unwrap();
+/// assert_eq!(addr, "8b2a5a4c86d0aabf939df9e88c907974f63d704d");
+/// ```
+pub fn contract_address_from_outpoint(
+    txid: &str,
+    vout: u32,
+) -> Result<HexAddress, QtumAddressError> {
+    if txid.is_empty() {
+        return Err(QtumAddressError::EmptyInput);
+    }
+    if txid.len() != TXID_LEN * 2 {
+        return Err(QtumAddressError::InvalidLength {
+            expected: TXID_LEN * 2,
+            found: txid.len(),
+        });
+    }
+
+    let mut preimage = hex::decode(txid).map_err(|_| QtumAddressError::InvalidHex)?;
+    preimage.reverse();
+    preimage.extend_from_slice(&vout.to_le_bytes());
+
+    Ok(HexAddress::from(hash160(&preimage)))
+}
+
+impl QtumAddress {
+    /// Base58 rendering of `contract_address_from_outpoint` with the pubkey hash byte of the
+    /// configured network, as block explorers show contracts
+    pub fn contract_from_outpoint(
+        &self,
+        txid: &str,
+        vout: u32,
+    ) -> Result<Base58Address, QtumAddressError> {
+        let contract = contract_address_from_outpoint(txid, vout)?;
+
+        Ok(self.from_hash160(&contract.to_bytes()))
+    }
+}
+
+/// RLP encoding of a string of at most 55 bytes
+fn rlp_append_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    if bytes.len() != 1 || bytes[0] >= RLP_STRING {
+        out.push(RLP_STRING + bytes.len() as u8);
+    }
+    out.extend_from_slice(bytes);
+}
+
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(data);
+    keccak.finalize(&mut hash);
+    hash
+}
+
+/// An EVM address is the low 20 bytes of a 32-byte hash
+fn last_20_bytes(hash: &[u8; 32]) -> [u8; ADDRESS_PAYLOAD_LEN] {
+    let mut address = [0u8; ADDRESS_PAYLOAD_LEN];
+    address.copy_from_slice(&hash[32 - ADDRESS_PAYLOAD_LEN..]);
+    address
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rlp_append_bytes;
+    use crate::*;
+    use alloc::vec;
+
+    const SENDER: &str = "6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+
+    #[test]
+    fn create_addresses_match_ethereum() {
+        for (nonce, expected) in &[
+            (0, "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            (1, "343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            (2, "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            (3, "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        ] {
+            assert_eq!(contract_address_create(SENDER, *nonce).unwrap(), *expected);
+        }
+    }
+
+    fn salt(hex: &str) -> [u8; 32] {
+        let mut salt = [0u8; 32];
+        hex::decode_to_slice(hex, &mut salt).unwrap();
+        salt
+    }
+
+    #[test]
+    fn create2_eip1014_examples() {
+        let zero = "0000000000000000000000000000000000000000";
+        let zero_salt = [0u8; 32];
+        let cafebabe = salt("00000000000000000000000000000000000000000000000000000000cafebabe");
+        let deadbeef = hex::decode("deadbeef").unwrap();
+
+        let examples: &[(&str, [u8; 32], Vec<u8>, &str)] = &[
+            (
+                zero,
+                zero_salt,
+                vec![0x00],
+                "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                zero_salt,
+                vec![0x00],
+                "b928f69bb1d91cd65274e3c79d8986362984fda3",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                salt("000000000000000000000000feed000000000000000000000000000000000000"),
+                vec![0x00],
+                "d04116cdd17bebe565eb2422f2497e06cc1c9833",
+            ),
+            (
+                zero,
+                zero_salt,
+                deadbeef.clone(),
+                "70f2b2914a2a4b783faefb75f459a580616fcb5e",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                cafebabe,
+                deadbeef.clone(),
+                "60f3f640a8508fc6a86d45df051962668e1e8ac7",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                cafebabe,
+                deadbeef.repeat(11),
+                "1d8bfdc5d46dc4f61d6b6115972536ebe6a8854c",
+            ),
+            (
+                zero,
+                zero_salt,
+                vec![],
+                "e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0",
+            ),
+        ];
+
+        for (sender, salt, init_code, expected) in examples {
+            assert_eq!(
+                contract_address_create2(sender, salt, init_code).unwrap(),
+                *expected
+            );
+        }
+    }
+
+    #[test]
+    fn create2_rejects_invalid_sender() {
+        assert_eq!(
+            contract_address_create2("0xhello", &[0; 32], &[]),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 5
+            })
+        );
+    }
+
+    #[test]
+    fn sender_is_a_hex_address() {
+        assert_eq!(
+            contract_address_create(&format!("0x{}", SENDER.to_uppercase()), 0),
+            contract_address_create(SENDER, 0)
+        );
+        assert_eq!(
+            contract_address_create("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", 0),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 34
+            })
+        );
+    }
+
+    #[test]
+    fn outpoint_contract_addresses() {
+        // Coinbase of Qtum mainnet block 4995, the address only depends on the outpoint
+        let txid = "54bc1f2a67b3c77c8035adce8f825dea3db3c17b8fc10b7c7a382aa1ccaa01f2";
+
+        for (txid, vout, hex, base58) in &[
+            (
+                txid,
+                0,
+                "8b2a5a4c86d0aabf939df9e88c907974f63d704d",
+                "QZHpmgYX38jtjLrYtVLgQo2iUmbrL2hMVV",
+            ),
+            (
+                txid,
+                0x0102_0304,
+                "bad0b54349a0f47a944b6e124e56261b617fddd5",
+                "QddmpGaeLChaMpYQyNcCwRJnpT8yyiBwBq",
+            ),
+            // The txid is reversed: its last display byte comes first
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                1,
+                "000aa5430afa31ccc8b023ff956f3111b37e1f2a",
+                "QLcCrxvPcDsEeS3ZRfQtCBxtw3wPyQkFcw",
+            ),
+        ] {
+            assert_eq!(contract_address_from_outpoint(txid, *vout).unwrap(), *hex);
+            assert_eq!(
+                QtumAddress::mainnet()
+                    .contract_from_outpoint(txid, *vout)
+                    .unwrap(),
+                *base58
+            );
+            assert_eq!(
+                QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+                    .contract_from_outpoint(txid, *vout)
+                    .unwrap(),
+                *base58
+            );
+        }
+
+        assert_eq!(
+            contract_address_from_outpoint(&txid.to_uppercase(), 0).unwrap(),
+            "8b2a5a4c86d0aabf939df9e88c907974f63d704d"
+        );
+    }
+
+    #[test]
+    fn outpoint_txid_validation() {
+        assert_eq!(
+            contract_address_from_outpoint("", 0),
+            Err(QtumAddressError::EmptyInput)
+        );
+        assert_eq!(
+            contract_address_from_outpoint(SENDER, 0),
+            Err(QtumAddressError::InvalidLength {
+                expected: 64,
+                found: 40
+            })
+        );
+        // Same length as a txid, but not all hex digits
+        let txid = "54bc1f2a67b3c77c8035adce8f825dea3db3c17b8fc10b7c7a382aa1ccaa01f2";
+        for invalid in &[format!("0x{}", &txid[2..]), format!("{}é", &txid[..62])] {
+            assert_eq!(
+                contract_address_from_outpoint(invalid, 0),
+                Err(QtumAddressError::InvalidHex)
+            );
+        }
+    }
+
+    #[test]
+    fn nonce_rlp_encoding() {
+        let encode = |bytes: &[u8]| {
+            let mut out = Vec::new();
+            rlp_append_bytes(&mut out, bytes);
+            out
+        };
+
+        assert_eq!(encode(&[]), [0x80]);
+        assert_eq!(encode(&[0x7f]), [0x7f]);
+        assert_eq!(encode(&[0x80]), [0x81, 0x80]);
+        assert_eq!(encode(&[0x04, 0x00]), [0x82, 0x04, 0x00]);
+
+        // Large nonces still produce distinct addresses
+        assert_ne!(
+            contract_address_create(SENDER, u64::MAX),
+            contract_address_create(SENDER, u64::MAX - 1)
+        );
+    }
+}
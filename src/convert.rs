@@ -0,0 +1,345 @@
+use crate::{
+    decode_address_bytes, encode_base58check, sanitize, AddressType, Base58Address, HexAddress,
+    QtumAddress, QtumAddressError, QtumNetwork, BITCOIN, HEX_ADDRESS_LEN, MAX_BASE58_ADDRESS_LEN,
+};
+use alloc::string::ToString;
+
+/// Format detected by `QtumAddress::convert`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    /// 40 hex characters with an optional `0x` prefix
+    Hex,
+    /// Base58check address
+    Base58,
+}
+
+/// Both forms of an address converted by `QtumAddress::convert`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Converted {
+    /// Base58 pubkeyhash address
+    pub base58: Base58Address,
+    /// Hex address for use in smart contracts
+    pub hex: HexAddress,
+    /// Format of the input
+    pub detected: InputKind,
+}
+
+impl QtumAddress {
+    /// Converts an address in either format, detecting whether it is hex or base58.
+    ///
+    /// Inputs starting with `0x` or made of exactly 40 hex characters are treated as hex,
+    /// other inputs made of base58 characters as base58 addresses of the configured network.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::new(QtumNetwork::Testnet);
+    /// let converted = qtum.convert("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68").unwrap();
+    ///
+    /// assert_eq!(converted.detected, InputKind::Hex);
+    /// assert_eq!(converted.base58, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+    /// ```
+    pub fn convert(&self, input: &str) -> Result<Converted, QtumAddressError> {
+        match detect_input_kind(input)? {
+            InputKind::Hex => {
+                let hash = self.decode_hex(input)?;
+                Ok(Converted {
+                    base58: self.encode_payload(&hash),
+                    hex: self.hex_format.format(&hash),
+                    detected: InputKind::Hex,
+                })
+            }
+            InputKind::Base58 => {
+                let hex = self.gethexaddress(input)?;
+                Ok(Converted {
+                    base58: Base58Address(input.to_string()),
+                    hex,
+                    detected: InputKind::Base58,
+                })
+            }
+        }
+    }
+
+    /// Re-encodes an address of the configured network and kind for `target`, keeping its
+    /// hash and kind.
+    ///
+    /// The checksum is always verified, whatever the converter's checksum setting, so a
+    /// mistyped address never comes out with a fresh valid checksum.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let mainnet = QtumAddress::testnet()
+    ///     .convert_network("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", QtumNetwork::Mainnet)
+    ///     .unwrap();
+    /// assert_eq!(mainnet, "QWVsxpNAd8UaWUCJSUKRGRHxkXnv71cAQo");
+    /// ```
+    pub fn convert_network(
+        &self,
+        address: &str,
+        target: QtumNetwork,
+    ) -> Result<Base58Address, QtumAddressError> {
+        let payload = self.decode(address, true)?;
+
+        let version = match self.kind {
+            AddressType::P2SH => target.to_script_prefix_byte(),
+            AddressType::P2PKH | AddressType::Contract => target.to_prefix_byte(),
+        };
+
+        Ok(Base58Address(encode_base58check(version, &payload[1..])))
+    }
+}
+
+/// Re-encodes a base58 address of one network for another, keeping hash and address kind.
+///
+/// The address must belong to `from`, a pubkey hash address stays a pubkey hash address
+/// and a script hash address a script hash address.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let testnet = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+/// let mainnet = convert_network(testnet, QtumNetwork::Testnet, QtumNetwork::Mainnet).unwrap();
+///
+/// assert_eq!(detect_network(&mainnet), Ok(QtumNetwork::Mainnet));
+/// assert_eq!(convert_network(&mainnet, QtumNetwork::Mainnet, QtumNetwork::Testnet).unwrap(), testnet);
+/// ```
+pub fn convert_network(
+    address: &str,
+    from: QtumNetwork,
+    to: QtumNetwork,
+) -> Result<Base58Address, QtumAddressError> {
+    let payload = decode_address_bytes(address, true)?;
+
+    let version = if payload[0] == from.to_prefix_byte() {
+        to.to_prefix_byte()
+    } else if payload[0] == from.to_script_prefix_byte() {
+        to.to_script_prefix_byte()
+    } else {
+        return Err(QtumAddressError::NetworkMismatch {
+            expected: from.to_prefix_byte(),
+            found: payload[0],
+        });
+    };
+
+    Ok(Base58Address(encode_base58check(version, &payload[1..])))
+}
+
+pub(crate) fn detect_input_kind(input: &str) -> Result<InputKind, QtumAddressError> {
+    if input.is_empty() {
+        return Err(QtumAddressError::EmptyInput);
+    }
+
+    let unprefixed = QtumAddress::removeprefix(input);
+    if unprefixed.len() != input.len()
+        || (input.len() == HEX_ADDRESS_LEN && input.bytes().all(|b| b.is_ascii_hexdigit()))
+    {
+        return Ok(InputKind::Hex);
+    }
+
+    if input.bytes().all(|b| BITCOIN.contains(&b)) {
+        return Ok(InputKind::Base58);
+    }
+
+    sanitize::check_characters(input, MAX_BASE58_ADDRESS_LEN + 1)?;
+
+    Err(QtumAddressError::UnrecognizedFormat)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn converter_convert_network() {
+        let testnet = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+        let mainnet = "QWVsxpNAd8UaWUCJSUKRGRHxkXnv71cAQo";
+
+        assert_eq!(
+            QtumAddress::testnet()
+                .convert_network(testnet, QtumNetwork::Mainnet)
+                .unwrap(),
+            mainnet
+        );
+        assert_eq!(
+            QtumAddress::mainnet()
+                .convert_network(mainnet, QtumNetwork::Testnet)
+                .unwrap(),
+            testnet
+        );
+        assert_eq!(
+            QtumAddress::testnet()
+                .convert_network(testnet, QtumNetwork::Testnet)
+                .unwrap(),
+            testnet
+        );
+
+        // Script hash converters keep the script hash kind
+        assert_eq!(
+            QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+                .convert_network("MHo45wyrwgmZy15cF7esPR7fiVjNNBfkMJ", QtumNetwork::Testnet)
+                .unwrap(),
+            "mS5FATs9YWa931SoiHdzUwSsUmBy28nHrr"
+        );
+    }
+
+    #[test]
+    fn converter_convert_network_checks_the_source() {
+        assert_eq!(
+            QtumAddress::mainnet()
+                .convert_network("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", QtumNetwork::Testnet),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+        assert_eq!(
+            QtumAddress::mainnet()
+                .convert_network("MHo45wyrwgmZy15cF7esPR7fiVjNNBfkMJ", QtumNetwork::Testnet),
+            Err(QtumAddressError::WrongAddressType {
+                expected: AddressType::P2PKH,
+                found: AddressType::P2SH
+            })
+        );
+
+        let lenient = QtumAddress::builder()
+            .network(QtumNetwork::Testnet)
+            .strict_checksum(false)
+            .build();
+        assert_eq!(
+            lenient.convert_network("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu", QtumNetwork::Mainnet),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn convert_network_round_trip() {
+        let testnet = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+        let mainnet = convert_network(testnet, QtumNetwork::Testnet, QtumNetwork::Mainnet).unwrap();
+        assert_eq!(QtumAddress::mainnet().gethexaddress(&mainnet).unwrap(), hex);
+        assert_eq!(
+            convert_network(&mainnet, QtumNetwork::Mainnet, QtumNetwork::Testnet).unwrap(),
+            testnet
+        );
+
+        let p2sh = QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+            .fromhexaddress(hex)
+            .unwrap();
+        let twin = convert_network(&p2sh, QtumNetwork::Mainnet, QtumNetwork::Testnet).unwrap();
+        assert_eq!(
+            Address::from_base58(&twin).unwrap(),
+            Address::new(
+                QtumNetwork::Testnet,
+                AddressType::P2SH,
+                hex.parse::<HexAddress>().unwrap().to_bytes()
+            )
+        );
+        assert_eq!(
+            convert_network(&twin, QtumNetwork::Testnet, QtumNetwork::Mainnet).unwrap(),
+            p2sh
+        );
+
+        assert_eq!(
+            convert_network(testnet, QtumNetwork::Mainnet, QtumNetwork::Testnet),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+        assert_eq!(
+            convert_network(
+                "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu",
+                QtumNetwork::Testnet,
+                QtumNetwork::Mainnet
+            ),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn address_with_network() {
+        let testnet = Address::from_base58("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+        let mainnet = testnet.with_network(QtumNetwork::Mainnet);
+
+        assert_eq!(mainnet.network(), QtumNetwork::Mainnet);
+        assert_eq!(mainnet.kind(), testnet.kind());
+        assert_eq!(mainnet.hash160(), testnet.hash160());
+        assert_eq!(
+            mainnet.to_base58(),
+            convert_network(
+                &testnet.to_base58(),
+                QtumNetwork::Testnet,
+                QtumNetwork::Mainnet
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn convert_mixed_column() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let rows = [
+            ("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", InputKind::Base58),
+            ("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68", InputKind::Hex),
+            ("0x2352be3db3177f0a07efbe6da5857615b8c9901d", InputKind::Hex),
+            ("0X2352BE3DB3177F0A07EFBE6DA5857615B8C9901D", InputKind::Hex),
+            ("qUbxboqjBRp96j3La8D1RYkyqx5uQbJPoW", InputKind::Base58),
+            ("69b004ac2b3993bf2fdf56b02746a1f57997420d", InputKind::Hex),
+        ];
+
+        for (input, kind) in rows.iter() {
+            let converted = qtum.convert(input).unwrap();
+            assert_eq!(converted.detected, *kind);
+
+            assert_eq!(
+                qtum.gethexaddress(&converted.base58).unwrap(),
+                converted.hex
+            );
+            assert_eq!(
+                qtum.fromhexaddress(&converted.hex).unwrap(),
+                converted.base58
+            );
+
+            match kind {
+                InputKind::Base58 => assert_eq!(converted.base58, *input),
+                InputKind::Hex => assert_eq!(
+                    converted.hex.as_str(),
+                    QtumAddress::removeprefix(input).to_lowercase()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn convert_rejects_neither() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(qtum.convert(""), Err(QtumAddressError::EmptyInput));
+        assert_eq!(
+            qtum.convert("not an address!"),
+            Err(QtumAddressError::Whitespace {
+                position: 3,
+                character: ' '
+            })
+        );
+        assert_eq!(
+            qtum.convert("qtum:qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::UnrecognizedFormat)
+        );
+        assert_eq!(
+            qtum.convert("0x6c89a1a6"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 8
+            })
+        );
+        assert_eq!(
+            qtum.convert("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+}
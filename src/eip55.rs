@@ -0,0 +1,90 @@
+use crate::{decode_hex, QtumAddress, QtumAddressError};
+use alloc::string::String;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Converts a hex address to its EIP-55 mixed-case checksummed form with the `0x` prefix.
+///
+/// The input may be in any case and carry an optional `0x` prefix.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let addr = to_eip55("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68").unwrap();
+/// assert!(verify_eip55(&addr));
+/// ```
+pub fn to_eip55(hex_address: &str) -> Result<String, QtumAddressError> {
+    let lower = hex::encode(decode_hex(hex_address)?);
+
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(lower.as_bytes());
+    keccak.finalize(&mut hash);
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = if i % 2 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0x0f
+        };
+        if nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+
+    Ok(checksummed)
+}
+
+/// Checks that a hex address carries a valid EIP-55 checksum, the `0x` prefix is optional
+pub fn verify_eip55(address: &str) -> bool {
+    match to_eip55(address) {
+        Ok(checksummed) => QtumAddress::removeprefix(address) == &checksummed[2..],
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors from EIP-55
+    const VECTORS: [&str; 8] = [
+        "0x52908400098527886E0F7030069857D2E4169EE7",
+        "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+        "0xde709f2102306220921060314715629080e2fb77",
+        "0x27b1fdb04752bbc536007a920d24acb045561c26",
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn eip55_vectors() {
+        for vector in VECTORS.iter() {
+            assert_eq!(to_eip55(&vector.to_lowercase()).unwrap(), *vector);
+            assert_eq!(to_eip55(&vector[2..].to_uppercase()).unwrap(), *vector);
+            assert!(verify_eip55(vector));
+            assert!(verify_eip55(&vector[2..]));
+        }
+    }
+
+    #[test]
+    fn eip55_rejects_wrong_case() {
+        assert!(!verify_eip55("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(!verify_eip55("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"));
+        assert!(!verify_eip55("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe"));
+
+        assert_eq!(
+            to_eip55("0x5aAeb6"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 6
+            })
+        );
+    }
+}
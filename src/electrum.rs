@@ -0,0 +1,103 @@
+//! Script hashes used by the Electrum protocol to subscribe to addresses
+
+use crate::hashes::sha256;
+use crate::script::p2pkh_script;
+use crate::{Address, AddressType, QtumAddress, QtumAddressError};
+use alloc::string::String;
+
+impl Address {
+    /// Electrum script hash of the address: the SHA256 of its locking script, byte-reversed
+    /// and hex encoded, as used by `blockchain.scripthash.subscribe`.
+    ///
+    /// Contract addresses are hashed as pubkey hash scripts, like their base58 form.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let addr = Address::from_base58("QVcyVJw6Fs9U6r7Emin9abs4r2rpPhBE1A").unwrap();
+    /// assert_eq!(
+    ///     addr.electrum_scripthash(),
+    ///     "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161"
+    /// );
+    /// ```
+    pub fn electrum_scripthash(&self) -> String {
+        let script = match self.kind() {
+            AddressType::P2SH => QtumAddress::to_p2sh_script(&self.hash160()),
+            AddressType::P2PKH | AddressType::Contract => p2pkh_script(&self.hash160()),
+        };
+
+        scripthash(&script)
+    }
+}
+
+impl QtumAddress {
+    /// Electrum script hash of a base58 or bech32 address of the configured network.
+    ///
+    /// Accepts every address `script_for` does, so P2WPKH and other witness addresses are
+    /// covered too.
+    pub fn electrum_scripthash(&self, address: &str) -> Result<String, QtumAddressError> {
+        Ok(scripthash(&self.script_for(address)?))
+    }
+}
+
+/// Byte-reversed hex SHA256 of a script
+fn scripthash(script: &[u8]) -> String {
+    let mut hash = sha256(script);
+    hash.reverse();
+    hex::encode(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// Hash160 of the bitcoin genesis block address `1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa`,
+    /// whose script hash is the example of the Electrum protocol documentation
+    const GENESIS_HASH: &str = "62e907b15cbf27d5425399ebf6f0fb50ebb88f18";
+    const GENESIS_SCRIPTHASH: &str =
+        "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161";
+
+    #[test]
+    fn documented_vector() {
+        let qtum = QtumAddress::mainnet();
+        let address = qtum.fromhexaddress(GENESIS_HASH).unwrap();
+        assert_eq!(
+            qtum.electrum_scripthash(&address).unwrap(),
+            GENESIS_SCRIPTHASH
+        );
+
+        // The script hash does not depend on the network
+        let testnet = Address::from_hex(GENESIS_HASH, QtumNetwork::Testnet).unwrap();
+        assert_eq!(testnet.to_base58(), "qSaNY3QxH3snoikcHjRueNjqsJqJWCn1gk");
+        assert_eq!(testnet.electrum_scripthash(), GENESIS_SCRIPTHASH);
+    }
+
+    #[test]
+    fn address_kinds() {
+        let qtum = QtumAddress::mainnet();
+
+        // sha256 of a9 14 <hash> 87 and 00 14 <hash>, reversed
+        for (address, scripthash) in &[
+            (
+                "MJpLas8xNugTnWRb13pqK2fbiu17ops1jJ",
+                "7a961a0e7c0f089527357fb0b8a7414761040bf0519bd8ddc94f30b9a6f1ac06",
+            ),
+            (
+                "qc1qw7llyrrqu53dl23n2rpekqc2t5qyaqu6s2zngy",
+                "9d676abfa21fc33cddfde9b6b1497765973e7c9dac0a247c4c3292f88e8aa519",
+            ),
+        ] {
+            assert_eq!(qtum.electrum_scripthash(address).unwrap(), *scripthash);
+        }
+
+        let p2sh = Address::from_base58("MJpLas8xNugTnWRb13pqK2fbiu17ops1jJ").unwrap();
+        assert_eq!(
+            p2sh.electrum_scripthash(),
+            "7a961a0e7c0f089527357fb0b8a7414761040bf0519bd8ddc94f30b9a6f1ac06"
+        );
+
+        assert!(qtum
+            .electrum_scripthash("qSaNY3QxH3snoikcHjRueNjqsJqJWCn1gk")
+            .is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use crate::address::classify_version;
+use crate::{AddressType, QtumNetwork};
+use alloc::string::String;
+use core::fmt;
+
+/// Errors returned by address conversion
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QtumAddressError {
+    /// The input string is empty
+    EmptyInput,
+    /// The input could not be decoded as base58
+    InvalidBase58,
+    /// The input contains a character outside of the base58 alphabet
+    #[non_exhaustive]
+    InvalidCharacter {
+        /// Character index of the first invalid character
+        position: usize,
+        /// The invalid character
+        character: char,
+    },
+    /// The input contains whitespace or an invisible character, usually picked up by copy and paste
+    #[non_exhaustive]
+    Whitespace {
+        /// Character index of the first offending character
+        position: usize,
+        /// The offending character
+        character: char,
+    },
+    /// The input contains a non-ASCII character that looks like an ASCII one
+    #[non_exhaustive]
+    Homoglyph {
+        /// Character index of the first offending character
+        position: usize,
+        /// The offending character
+        character: char,
+        /// The ASCII character it resembles
+        lookalike: char,
+    },
+    /// The input is not a valid hex string
+    InvalidHex,
+    /// The input or its decoded bytes have an unexpected length
+    #[non_exhaustive]
+    InvalidLength {
+        /// Expected length
+        expected: usize,
+        /// Actual length
+        found: usize,
+    },
+    /// The input is longer than any valid address
+    #[non_exhaustive]
+    TooLong {
+        /// Maximum accepted length
+        max: usize,
+        /// Actual length
+        found: usize,
+    },
+    /// The base58check checksum does not match the payload
+    InvalidChecksum,
+    /// The address prefix does not match the network of the converter
+    #[non_exhaustive]
+    NetworkMismatch {
+        /// Prefix byte of the converter
+        expected: u8,
+        /// Prefix byte of the address
+        found: u8,
+    },
+    /// The address belongs to the configured network but is of the other kind,
+    /// e.g. a script hash address given to a pubkey hash converter
+    #[non_exhaustive]
+    WrongAddressType {
+        /// Kind the converter works with
+        expected: AddressType,
+        /// Kind of the address
+        found: AddressType,
+    },
+    /// The prefix byte does not belong to any known network
+    #[non_exhaustive]
+    UnknownNetwork {
+        /// Offending prefix byte
+        prefix: u8,
+    },
+    /// The input is neither a hex nor a base58 address
+    UnrecognizedFormat,
+    /// The bytes are not a valid secp256k1 public key in the expected encoding
+    InvalidPublicKey,
+    /// The bytes are not a valid secp256k1 private key or WIF compression flag
+    InvalidPrivateKey,
+    /// The input is not a well formed bech32 string
+    InvalidBech32,
+    /// The bech32 prefix does not match the network of the converter
+    #[non_exhaustive]
+    HrpMismatch {
+        /// Prefix of the converter's network
+        expected: &'static str,
+        /// Prefix of the address
+        found: String,
+    },
+    /// The witness version or program length breaks the segwit rules
+    #[non_exhaustive]
+    InvalidWitnessProgram {
+        /// Witness version
+        version: u8,
+        /// Program length in bytes
+        length: usize,
+    },
+    /// The witness program is not a 20-byte pubkey hash and has no hex address form
+    #[non_exhaustive]
+    UnrepresentableWitnessProgram {
+        /// Witness version
+        version: u8,
+        /// Program length in bytes
+        length: usize,
+    },
+    /// The 12 bytes in front of an address in its ABI word are not zero
+    #[non_exhaustive]
+    InvalidAbiPadding {
+        /// Index of the first non-zero padding byte
+        position: usize,
+    },
+    /// The input is not a valid `qtum:` payment URI
+    InvalidUri,
+    /// The payment amount is negative, not finite or has more than 8 decimals
+    InvalidAmount,
+    /// The serialized transaction is truncated or malformed
+    #[non_exhaustive]
+    InvalidTransaction {
+        /// Byte offset at which parsing failed
+        offset: usize,
+    },
+    /// The signature threshold or number of keys of a multisig script is out of range
+    #[non_exhaustive]
+    InvalidMultisig {
+        /// Required number of signatures
+        required: u8,
+        /// Number of public keys
+        keys: usize,
+    },
+    /// A push in the script runs past the end of the script
+    #[non_exhaustive]
+    InvalidScript {
+        /// Byte offset of the push opcode
+        offset: usize,
+    },
+    /// The redeem script is larger than the consensus limit
+    #[non_exhaustive]
+    ScriptTooLong {
+        /// Maximum script size in bytes
+        max: usize,
+        /// Actual script size in bytes
+        found: usize,
+    },
+}
+
+impl fmt::Display for QtumAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QtumAddressError::EmptyInput => write!(f, "Invalid address: empty input"),
+            QtumAddressError::InvalidBase58 => write!(f, "Invalid address: not a base58 string"),
+            QtumAddressError::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "Invalid address: invalid character {:?} at position {} (0, O, I and l are never part of a valid address)",
+                character, position
+            ),
+            QtumAddressError::Whitespace {
+                position,
+                character,
+            } => write!(
+                f,
+                "Invalid address: input contains whitespace or invisible character U+{:04X} at position {}",
+                *character as u32, position
+            ),
+            QtumAddressError::Homoglyph {
+                position,
+                character,
+                lookalike,
+            } => write!(
+                f,
+                "Invalid address: character {:?} (U+{:04X}) at position {} looks like {:?} but is not ASCII",
+                character, *character as u32, position, lookalike
+            ),
+            QtumAddressError::InvalidHex => write!(f, "Invalid address: not a hex string"),
+            QtumAddressError::InvalidLength { expected, found } => write!(
+                f,
+                "Invalid address: expected length {}, found {}",
+                expected, found
+            ),
+            QtumAddressError::TooLong { max, found } => write!(
+                f,
+                "Invalid address: input is {} characters long, at most {} are allowed",
+                found, max
+            ),
+            QtumAddressError::InvalidChecksum => write!(f, "Invalid address: checksum mismatch"),
+            QtumAddressError::NetworkMismatch { expected, found } => write!(
+                f,
+                "Invalid address: address belongs to {} but converter is configured for {}",
+                PrefixNetwork(*found),
+                PrefixNetwork(*expected)
+            ),
+            QtumAddressError::WrongAddressType { expected, found } => write!(
+                f,
+                "Invalid address: not a {} address but a {} address",
+                kind_name(*expected),
+                kind_name(*found)
+            ),
+            QtumAddressError::UnknownNetwork { prefix } => {
+                write!(f, "Invalid address: unknown network prefix {:#04x}", prefix)
+            }
+            QtumAddressError::UnrecognizedFormat => write!(
+                f,
+                "Invalid address: input is neither a hex address nor a base58 address"
+            ),
+            QtumAddressError::InvalidPublicKey => write!(f, "Invalid public key"),
+            QtumAddressError::InvalidPrivateKey => write!(f, "Invalid private key"),
+            QtumAddressError::InvalidBech32 => write!(f, "Invalid address: not a bech32 string"),
+            QtumAddressError::HrpMismatch { expected, found } => write!(
+                f,
+                "Invalid address: bech32 prefix {:?} but converter expects {:?}",
+                found, expected
+            ),
+            QtumAddressError::InvalidWitnessProgram { version, length } => write!(
+                f,
+                "Invalid address: {} byte witness program is not valid for witness version {}",
+                length, version
+            ),
+            QtumAddressError::UnrepresentableWitnessProgram { version, length } => write!(
+                f,
+                "Invalid address: version {} witness program of {} bytes is not representable as a 20-byte EVM address",
+                version, length
+            ),
+            QtumAddressError::InvalidAbiPadding { position } => write!(
+                f,
+                "Invalid address: ABI word has a non-zero padding byte at position {}",
+                position
+            ),
+            QtumAddressError::InvalidUri => write!(f, "Invalid payment URI"),
+            QtumAddressError::InvalidAmount => write!(f, "Invalid payment amount"),
+            QtumAddressError::InvalidTransaction { offset } => {
+                write!(f, "Invalid transaction: malformed or truncated at byte {}", offset)
+            }
+            QtumAddressError::InvalidMultisig { required, keys } => write!(
+                f,
+                "Invalid multisig: {} of {} keys, 1 <= m <= n <= 16 is required",
+                required, keys
+            ),
+            QtumAddressError::InvalidScript { offset } => write!(
+                f,
+                "Invalid script: push at byte {} runs past the end of the script",
+                offset
+            ),
+            QtumAddressError::ScriptTooLong { max, found } => write!(
+                f,
+                "Invalid script: {} bytes long, redeem scripts are limited to {} bytes",
+                found, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QtumAddressError {}
+
+/// Network of a prefix byte and the byte, `testnet (0x78)`, or `custom(0x55)` for a prefix
+/// of no known network
+struct PrefixNetwork(u8);
+
+impl fmt::Display for PrefixNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match classify_version(self.0) {
+            Some((network, _)) => write!(f, "{} ({:#04x})", network.name(), self.0),
+            None => fmt::Display::fmt(&QtumNetwork::Custom(self.0), f),
+        }
+    }
+}
+
+/// Human readable name of an address kind
+fn kind_name(kind: AddressType) -> &'static str {
+    match kind {
+        AddressType::P2PKH => "pubkeyhash",
+        AddressType::P2SH => "scripthash",
+        AddressType::Contract => "contract",
+    }
+}
+
+/// Error returned when parsing an unknown network name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNetworkError {
+    pub(crate) name: String,
+}
+
+impl ParseNetworkError {
+    /// The name that was not recognized
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for ParseNetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown network {:?}, expected one of mainnet, main, testnet, test, regtest or custom(0xNN)",
+            self.name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseNetworkError {}
@@ -0,0 +1,143 @@
+//! Extension traits for converting strings and hashes in place
+
+use crate::{
+    to_base58, to_hex, Base58Address, HexAddress, QtumAddress, QtumAddressError, QtumNetwork,
+    ADDRESS_PAYLOAD_LEN,
+};
+use alloc::string::String;
+
+/// Conversion to a hex address.
+///
+/// Strings are read as base58 addresses of the given network, a raw hash160 is only formatted.
+///
+/// ```rust
+/// use qtum_address_rust::prelude::*;
+///
+/// # fn main() -> Result<(), QtumAddressError> {
+/// let hex = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".to_hex_address(QtumNetwork::Testnet)?;
+/// assert_eq!(hex, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+/// # Ok(())
+/// # }
+/// ```
+pub trait ToHexAddress {
+    /// Converting to a hex address, checking the address against `network`
+    fn to_hex_address(&self, network: QtumNetwork) -> Result<HexAddress, QtumAddressError>;
+}
+
+/// Conversion to a base58 pubkeyhash address.
+///
+/// Strings are read as hex addresses, a raw hash160 is encoded as is.
+///
+/// ```rust
+/// use qtum_address_rust::prelude::*;
+///
+/// # fn main() -> Result<(), QtumAddressError> {
+/// let qtum_addr = "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68".to_base58_address(QtumNetwork::Testnet)?;
+/// assert_eq!(qtum_addr, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+/// # Ok(())
+/// # }
+/// ```
+pub trait ToBase58Address {
+    /// Converting to a base58 address of `network`
+    fn to_base58_address(&self, network: QtumNetwork) -> Result<Base58Address, QtumAddressError>;
+}
+
+impl ToHexAddress for str {
+    fn to_hex_address(&self, network: QtumNetwork) -> Result<HexAddress, QtumAddressError> {
+        to_hex(self, network)
+    }
+}
+
+impl ToHexAddress for String {
+    fn to_hex_address(&self, network: QtumNetwork) -> Result<HexAddress, QtumAddressError> {
+        to_hex(self, network)
+    }
+}
+
+impl ToHexAddress for [u8; ADDRESS_PAYLOAD_LEN] {
+    /// Formatting the hash160, never fails since a hex address carries no network
+    fn to_hex_address(&self, _network: QtumNetwork) -> Result<HexAddress, QtumAddressError> {
+        Ok(HexAddress::from(*self))
+    }
+}
+
+impl ToBase58Address for str {
+    fn to_base58_address(&self, network: QtumNetwork) -> Result<Base58Address, QtumAddressError> {
+        to_base58(self, network)
+    }
+}
+
+impl ToBase58Address for String {
+    fn to_base58_address(&self, network: QtumNetwork) -> Result<Base58Address, QtumAddressError> {
+        to_base58(self, network)
+    }
+}
+
+impl ToBase58Address for [u8; ADDRESS_PAYLOAD_LEN] {
+    /// Encoding the hash160, never fails
+    fn to_base58_address(&self, network: QtumNetwork) -> Result<Base58Address, QtumAddressError> {
+        Ok(QtumAddress::new(network).from_hash160(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const HASH: [u8; 20] = [
+        0x6c, 0x89, 0xa1, 0xa6, 0xca, 0x2a, 0xe7, 0xc0, 0x0b, 0x24, 0x8b, 0xb2, 0x83, 0x2d, 0x6f,
+        0x48, 0x0f, 0x27, 0xda, 0x68,
+    ];
+
+    #[test]
+    fn byte_array_to_base58() {
+        assert_eq!(
+            HASH.to_base58_address(QtumNetwork::Testnet).unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+
+        let mainnet = HASH.to_base58_address(QtumNetwork::Mainnet).unwrap();
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Mainnet)
+                .gethexaddress(&mainnet)
+                .unwrap(),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+    }
+
+    #[test]
+    fn byte_array_to_hex() {
+        for network in QtumNetwork::all() {
+            assert_eq!(
+                HASH.to_hex_address(*network).unwrap(),
+                "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+            );
+        }
+        assert_eq!(
+            [0u8; 20].to_hex_address(QtumNetwork::Mainnet).unwrap(),
+            "0000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn strings_match_struct_methods() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let base58 = String::from("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+
+        assert_eq!(
+            base58.to_hex_address(QtumNetwork::Testnet),
+            qtum.gethexaddress(&base58)
+        );
+        assert_eq!(
+            base58.to_hex_address(QtumNetwork::Mainnet),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+        assert_eq!(
+            "0xhello".to_base58_address(QtumNetwork::Testnet),
+            qtum.fromhexaddress("0xhello")
+        );
+    }
+}
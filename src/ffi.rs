@@ -0,0 +1,253 @@
+//! C interface, enabled with the `ffi` feature
+//!
+//! Every function returns `QTUM_OK` on success and writes a NUL-terminated string to `out`,
+//! on failure a negative `QTUM_ERR_*` code is returned and `out` is left untouched.
+//! The C header is generated with `cbindgen --config cbindgen.toml --output include/qtum_address.h`.
+use crate::{QtumAddress, QtumAddressError, QtumNetwork};
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+/// Success
+pub const QTUM_OK: c_int = 0;
+/// `QtumAddressError::EmptyInput`
+pub const QTUM_ERR_EMPTY_INPUT: c_int = -1;
+/// `QtumAddressError::InvalidBase58`
+pub const QTUM_ERR_INVALID_BASE58: c_int = -2;
+/// `QtumAddressError::InvalidCharacter`
+pub const QTUM_ERR_INVALID_CHARACTER: c_int = -3;
+/// `QtumAddressError::Whitespace`
+pub const QTUM_ERR_WHITESPACE: c_int = -4;
+/// `QtumAddressError::Homoglyph`
+pub const QTUM_ERR_HOMOGLYPH: c_int = -5;
+/// `QtumAddressError::InvalidHex`
+pub const QTUM_ERR_INVALID_HEX: c_int = -6;
+/// `QtumAddressError::InvalidLength`
+pub const QTUM_ERR_INVALID_LENGTH: c_int = -7;
+/// `QtumAddressError::TooLong`
+pub const QTUM_ERR_TOO_LONG: c_int = -8;
+/// `QtumAddressError::InvalidChecksum`
+pub const QTUM_ERR_INVALID_CHECKSUM: c_int = -9;
+/// `QtumAddressError::NetworkMismatch`
+pub const QTUM_ERR_NETWORK_MISMATCH: c_int = -10;
+/// `QtumAddressError::UnknownNetwork`
+pub const QTUM_ERR_UNKNOWN_NETWORK: c_int = -11;
+/// `QtumAddressError::UnrecognizedFormat`
+pub const QTUM_ERR_UNRECOGNIZED_FORMAT: c_int = -12;
+/// `QtumAddressError::WrongAddressType`
+pub const QTUM_ERR_WRONG_ADDRESS_TYPE: c_int = -13;
+/// `QtumAddressError::InvalidPublicKey`
+pub const QTUM_ERR_INVALID_PUBLIC_KEY: c_int = -14;
+/// `QtumAddressError::ScriptTooLong`
+pub const QTUM_ERR_SCRIPT_TOO_LONG: c_int = -15;
+/// `QtumAddressError::InvalidMultisig`
+pub const QTUM_ERR_INVALID_MULTISIG: c_int = -16;
+/// `QtumAddressError::InvalidPrivateKey`
+pub const QTUM_ERR_INVALID_PRIVATE_KEY: c_int = -17;
+/// `QtumAddressError::InvalidBech32`
+pub const QTUM_ERR_INVALID_BECH32: c_int = -18;
+/// `QtumAddressError::HrpMismatch`
+pub const QTUM_ERR_HRP_MISMATCH: c_int = -19;
+/// `QtumAddressError::InvalidWitnessProgram`
+pub const QTUM_ERR_INVALID_WITNESS_PROGRAM: c_int = -20;
+/// `QtumAddressError::UnrepresentableWitnessProgram`
+pub const QTUM_ERR_UNREPRESENTABLE_WITNESS_PROGRAM: c_int = -21;
+/// `QtumAddressError::InvalidAbiPadding`
+pub const QTUM_ERR_INVALID_ABI_PADDING: c_int = -22;
+/// `QtumAddressError::InvalidUri`
+pub const QTUM_ERR_INVALID_URI: c_int = -23;
+/// `QtumAddressError::InvalidAmount`
+pub const QTUM_ERR_INVALID_AMOUNT: c_int = -24;
+/// `QtumAddressError::InvalidTransaction`
+pub const QTUM_ERR_INVALID_TRANSACTION: c_int = -25;
+/// `QtumAddressError::InvalidScript`
+pub const QTUM_ERR_INVALID_SCRIPT: c_int = -26;
+/// `address` or `out` is a null pointer
+pub const QTUM_ERR_NULL_POINTER: c_int = -100;
+/// `address` is not valid UTF-8
+pub const QTUM_ERR_INVALID_UTF8: c_int = -101;
+/// `out_len` is too small for the result and its NUL terminator
+pub const QTUM_ERR_BUFFER_TOO_SMALL: c_int = -102;
+
+/// Converts a base58 pubkeyhash address to a hex address.
+///
+/// # Safety
+///
+/// `address` must be a valid NUL-terminated string and `out` must point to at least `out_len`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn qtum_gethexaddress(
+    prefix: u8,
+    address: *const c_char,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    convert(prefix, address, out, out_len, |qtum, address| {
+        qtum.gethexaddress(address).map(String::from)
+    })
+}
+
+/// Converts a raw hex address to a base58 pubkeyhash address.
+///
+/// # Safety
+///
+/// `address` must be a valid NUL-terminated string and `out` must point to at least `out_len`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn qtum_fromhexaddress(
+    prefix: u8,
+    address: *const c_char,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    convert(prefix, address, out, out_len, |qtum, address| {
+        qtum.fromhexaddress(address).map(String::from)
+    })
+}
+
+unsafe fn convert<F>(
+    prefix: u8,
+    address: *const c_char,
+    out: *mut c_char,
+    out_len: usize,
+    f: F,
+) -> c_int
+where
+    F: FnOnce(&QtumAddress, &str) -> Result<String, QtumAddressError>,
+{
+    if address.is_null() || out.is_null() {
+        return QTUM_ERR_NULL_POINTER;
+    }
+
+    let address = match CStr::from_ptr(address).to_str() {
+        Ok(address) => address,
+        Err(_) => return QTUM_ERR_INVALID_UTF8,
+    };
+
+    let result =
+        QtumNetwork::try_from(prefix).and_then(|network| f(&QtumAddress::new(network), address));
+
+    match result {
+        Ok(converted) => {
+            if converted.len() >= out_len {
+                return QTUM_ERR_BUFFER_TOO_SMALL;
+            }
+
+            ptr::copy_nonoverlapping(converted.as_ptr() as *const c_char, out, converted.len());
+            *out.add(converted.len()) = 0;
+
+            QTUM_OK
+        }
+        Err(err) => error_code(&err),
+    }
+}
+
+fn error_code(err: &QtumAddressError) -> c_int {
+    match err {
+        QtumAddressError::EmptyInput => QTUM_ERR_EMPTY_INPUT,
+        QtumAddressError::InvalidBase58 => QTUM_ERR_INVALID_BASE58,
+        QtumAddressError::InvalidCharacter { .. } => QTUM_ERR_INVALID_CHARACTER,
+        QtumAddressError::Whitespace { .. } => QTUM_ERR_WHITESPACE,
+        QtumAddressError::Homoglyph { .. } => QTUM_ERR_HOMOGLYPH,
+        QtumAddressError::InvalidHex => QTUM_ERR_INVALID_HEX,
+        QtumAddressError::InvalidLength { .. } => QTUM_ERR_INVALID_LENGTH,
+        QtumAddressError::TooLong { .. } => QTUM_ERR_TOO_LONG,
+        QtumAddressError::InvalidChecksum => QTUM_ERR_INVALID_CHECKSUM,
+        QtumAddressError::NetworkMismatch { .. } => QTUM_ERR_NETWORK_MISMATCH,
+        QtumAddressError::UnknownNetwork { .. } => QTUM_ERR_UNKNOWN_NETWORK,
+        QtumAddressError::UnrecognizedFormat => QTUM_ERR_UNRECOGNIZED_FORMAT,
+        QtumAddressError::WrongAddressType { .. } => QTUM_ERR_WRONG_ADDRESS_TYPE,
+        QtumAddressError::InvalidPublicKey => QTUM_ERR_INVALID_PUBLIC_KEY,
+        QtumAddressError::ScriptTooLong { .. } => QTUM_ERR_SCRIPT_TOO_LONG,
+        QtumAddressError::InvalidScript { .. } => QTUM_ERR_INVALID_SCRIPT,
+        QtumAddressError::InvalidMultisig { .. } => QTUM_ERR_INVALID_MULTISIG,
+        QtumAddressError::InvalidAbiPadding { .. } => QTUM_ERR_INVALID_ABI_PADDING,
+        QtumAddressError::InvalidUri => QTUM_ERR_INVALID_URI,
+        QtumAddressError::InvalidAmount => QTUM_ERR_INVALID_AMOUNT,
+        QtumAddressError::InvalidTransaction { .. } => QTUM_ERR_INVALID_TRANSACTION,
+        QtumAddressError::InvalidPrivateKey => QTUM_ERR_INVALID_PRIVATE_KEY,
+        QtumAddressError::InvalidBech32 => QTUM_ERR_INVALID_BECH32,
+        QtumAddressError::HrpMismatch { .. } => QTUM_ERR_HRP_MISMATCH,
+        QtumAddressError::InvalidWitnessProgram { .. } => QTUM_ERR_INVALID_WITNESS_PROGRAM,
+        QtumAddressError::UnrepresentableWitnessProgram { .. } => {
+            QTUM_ERR_UNREPRESENTABLE_WITNESS_PROGRAM
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    fn call(
+        f: unsafe extern "C" fn(u8, *const c_char, *mut c_char, usize) -> c_int,
+        prefix: u8,
+        input: &str,
+        out_len: usize,
+    ) -> (c_int, String) {
+        let input = CString::new(input).unwrap();
+        let mut out = vec![0 as c_char; out_len.max(1)];
+
+        let code = unsafe { f(prefix, input.as_ptr(), out.as_mut_ptr(), out_len) };
+        let out = unsafe { CStr::from_ptr(out.as_ptr()) };
+
+        (code, out.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn converts_both_ways() {
+        assert_eq!(
+            call(
+                qtum_gethexaddress,
+                0x78,
+                "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+                64
+            ),
+            (
+                QTUM_OK,
+                "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68".to_string()
+            )
+        );
+        assert_eq!(
+            call(
+                qtum_fromhexaddress,
+                0x78,
+                "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+                64
+            ),
+            (QTUM_OK, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        let addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+
+        assert_eq!(
+            call(qtum_gethexaddress, 0x3a, addr, 64).0,
+            QTUM_ERR_NETWORK_MISMATCH
+        );
+        assert_eq!(
+            call(qtum_gethexaddress, 0x00, addr, 64).0,
+            QTUM_ERR_NETWORK_MISMATCH
+        );
+        assert_eq!(
+            call(qtum_gethexaddress, 0x78, "", 64).0,
+            QTUM_ERR_EMPTY_INPUT
+        );
+        assert_eq!(
+            call(qtum_gethexaddress, 0x78, addr, 40).0,
+            QTUM_ERR_BUFFER_TOO_SMALL
+        );
+        assert_eq!(
+            call(qtum_fromhexaddress, 0x78, "zz", 64).0,
+            QTUM_ERR_INVALID_LENGTH
+        );
+
+        let mut out = [0 as c_char; 64];
+        let code = unsafe { qtum_gethexaddress(0x78, ptr::null(), out.as_mut_ptr(), 64) };
+        assert_eq!(code, QTUM_ERR_NULL_POINTER);
+    }
+}
@@ -0,0 +1,58 @@
+//! Hash functions of Qtum addresses and scripts, on top of `bitcoin_hashes`
+
+use bitcoin_hashes::Hash;
+use bitcoin_hashes::{hash160 as hash160_hash, sha256 as sha256_hash, sha256d as sha256d_hash};
+
+/// `RIPEMD160(SHA256(data))`, the hash of pubkey hash and script hash addresses
+///
+/// ```rust
+/// use qtum_address_rust::hashes::hash160;
+///
+/// let pubkey = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+/// assert_eq!(hex::encode(hash160(&pubkey)), "751e76e8199196d454941c45d1b3a323f1433bd6");
+/// ```
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    hash160_hash::Hash::hash(data).into_inner()
+}
+
+/// Single SHA256, the hash of P2WSH witness programs
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    sha256_hash::Hash::hash(data).into_inner()
+}
+
+/// `SHA256(SHA256(data))`, the hash of base58check checksums and transaction ids
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    sha256d_hash::Hash::hash(data).into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hashes::*;
+
+    #[test]
+    fn reference_values() {
+        assert_eq!(
+            hex::encode(hash160(b"")),
+            "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb"
+        );
+        assert_eq!(
+            hex::encode(hash160(
+                &hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                    .unwrap()
+            )),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+        assert_eq!(
+            hex::encode(sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex::encode(sha256d(b"")),
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+        assert_eq!(
+            hex::encode(sha256d(b"hello")),
+            "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50"
+        );
+    }
+}
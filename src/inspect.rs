@@ -0,0 +1,110 @@
+use crate::address::classify_version;
+use crate::{
+    checksum, decode_base58, AddressType, QtumAddress, QtumAddressError, QtumNetwork,
+    ADDRESS_PAYLOAD_LEN, CHECKSUM_LEN, VERSIONED_PAYLOAD_LEN,
+};
+
+/// Decomposition of a base58 address, see `QtumAddress::inspect`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AddressInfo {
+    /// Raw version byte
+    pub version: u8,
+    /// Network of the version byte, `None` if unknown
+    pub network: Option<QtumNetwork>,
+    /// Kind of the version byte, `None` if unknown
+    pub kind: Option<AddressType>,
+    /// Embedded 20-byte hash
+    pub hash160: [u8; ADDRESS_PAYLOAD_LEN],
+    /// Checksum carried by the address
+    pub checksum: [u8; CHECKSUM_LEN],
+    /// Checksum recomputed from version byte and hash
+    pub computed_checksum: [u8; CHECKSUM_LEN],
+    /// Whether both checksums match
+    pub checksum_valid: bool,
+}
+
+impl QtumAddress {
+    /// Decomposes a base58 address into its parts.
+    ///
+    /// Unlike `gethexaddress` this succeeds for unknown version bytes and wrong checksums,
+    /// only input that does not decode to 25 bytes is an error.
+    pub fn inspect(address: &str) -> Result<AddressInfo, QtumAddressError> {
+        let bytes = decode_base58(address)?;
+
+        let version = bytes[0];
+        let classified = classify_version(version);
+
+        let mut hash160 = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash160.copy_from_slice(&bytes[1..VERSIONED_PAYLOAD_LEN]);
+
+        let mut embedded = [0u8; CHECKSUM_LEN];
+        embedded.copy_from_slice(&bytes[VERSIONED_PAYLOAD_LEN..]);
+        let computed = checksum(&bytes[..VERSIONED_PAYLOAD_LEN]);
+
+        Ok(AddressInfo {
+            version,
+            network: classified.map(|(network, _)| network),
+            kind: classified.map(|(_, kind)| kind),
+            hash160,
+            checksum: embedded,
+            computed_checksum: computed,
+            checksum_valid: embedded == computed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const HEX: &str = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+    #[test]
+    fn inspect_valid_address() {
+        let info = QtumAddress::inspect("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+
+        assert_eq!(info.version, 0x78);
+        assert_eq!(info.network, Some(QtumNetwork::Testnet));
+        assert_eq!(info.kind, Some(AddressType::P2PKH));
+        assert_eq!(hex::encode(info.hash160), HEX);
+        assert!(info.checksum_valid);
+        assert_eq!(info.checksum, info.computed_checksum);
+    }
+
+    #[test]
+    fn inspect_corrupted_checksum() {
+        let info = QtumAddress::inspect("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu").unwrap();
+
+        assert_eq!(info.network, Some(QtumNetwork::Testnet));
+        assert_eq!(hex::encode(info.hash160), HEX);
+        assert!(!info.checksum_valid);
+        assert_ne!(info.checksum, info.computed_checksum);
+    }
+
+    #[test]
+    fn inspect_script_hash_and_unknown_versions() {
+        let hash = decode_hex(HEX).unwrap();
+
+        let p2sh = QtumAddress::inspect(&encode_base58check(0x32, &hash)).unwrap();
+        assert_eq!(p2sh.network, Some(QtumNetwork::Mainnet));
+        assert_eq!(p2sh.kind, Some(AddressType::P2SH));
+        assert!(p2sh.checksum_valid);
+
+        let unknown = QtumAddress::inspect(&encode_base58check(0x00, &hash)).unwrap();
+        assert_eq!(unknown.version, 0x00);
+        assert_eq!(unknown.network, None);
+        assert_eq!(unknown.kind, None);
+    }
+
+    #[test]
+    fn inspect_undecodable_input() {
+        assert_eq!(
+            QtumAddress::inspect("qTTH1Yr2eK"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 25,
+                found: 8
+            })
+        );
+    }
+}
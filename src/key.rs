@@ -0,0 +1,314 @@
+//! Addresses derived from public keys, enabled with the `key_derivation` feature
+
+use crate::{
+    decode_base58_input, hash160, split_checksum, Base58Address, QtumAddress, QtumAddressError,
+    QtumNetwork, CHECKSUM_LEN,
+};
+use alloc::vec::Vec;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{PublicKey, SecretKey};
+
+/// Length of a secp256k1 private key
+const SECRET_KEY_LEN: usize = 32;
+
+/// Raw length of an uncompressed WIF: version byte, key and checksum
+const WIF_LEN: usize = 1 + SECRET_KEY_LEN + CHECKSUM_LEN;
+
+/// Trailing byte of the key marking a WIF of a compressed public key
+const WIF_COMPRESSED_FLAG: u8 = 0x01;
+
+/// Longest base58 encoding of a compressed WIF
+const MAX_WIF_LEN: usize = 52;
+
+/// Derives the pubkey hash address of a WIF encoded private key.
+///
+/// A WIF ending with the compression flag gives the address of the compressed public key,
+/// otherwise the address of the uncompressed one. The version byte must be the WIF prefix of
+/// `network`; `Custom` networks have none and give `UnknownNetwork`.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let addr = from_wif(
+///     "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+///     QtumNetwork::Mainnet,
+/// )
+/// .unwrap();
+/// assert_eq!(addr, "QXHFfTBKYXjaaTH1e7Rox8CcdNPGHVhM59");
+/// ```
+pub fn from_wif(wif: &str, network: QtumNetwork) -> Result<Base58Address, QtumAddressError> {
+    let prefix = match network.params_or_prefix() {
+        Ok(params) => params.wif,
+        Err(prefix) => return Err(QtumAddressError::UnknownNetwork { prefix }),
+    };
+
+    let bytes = split_checksum(decode_wif(wif)?, true)?;
+
+    if bytes[0] != prefix {
+        return Err(QtumAddressError::NetworkMismatch {
+            expected: prefix,
+            found: bytes[0],
+        });
+    }
+
+    let compressed = match bytes.len() - 1 {
+        SECRET_KEY_LEN => false,
+        _ if bytes[1 + SECRET_KEY_LEN] == WIF_COMPRESSED_FLAG => true,
+        _ => return Err(QtumAddressError::InvalidPrivateKey),
+    };
+
+    let secret_key = SecretKey::from_slice(&bytes[1..1 + SECRET_KEY_LEN])
+        .map_err(|_| QtumAddressError::InvalidPrivateKey)?;
+    let pubkey = secret_key.public_key().to_encoded_point(compressed);
+
+    Ok(QtumAddress::new(network).from_hash160(&hash160(pubkey.as_bytes())))
+}
+
+/// Decoding the base58 of a WIF into its raw bytes, the checksum is not verified
+fn decode_wif(wif: &str) -> Result<Vec<u8>, QtumAddressError> {
+    let bytes = decode_base58_input(wif, MAX_WIF_LEN)?;
+
+    if bytes.len() != WIF_LEN && bytes.len() != WIF_LEN + 1 {
+        return Err(QtumAddressError::InvalidLength {
+            expected: WIF_LEN,
+            found: bytes.len(),
+        });
+    }
+
+    Ok(bytes)
+}
+
+/// Derives the pubkey hash address of a 33-byte compressed secp256k1 public key:
+/// base58check of RIPEMD160(SHA256(pubkey)) with the network prefix.
+///
+/// Keys not starting with `0x02` or `0x03`, or not on the curve, give `InvalidPublicKey`.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let mut pubkey = [0u8; 33];
+/// hex::decode_to_slice(
+///     "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+///     &mut pubkey,
+/// )
+/// .unwrap();
+///
+/// let addr = from_compressed_pubkey(&pubkey, QtumNetwork::Mainnet).unwrap();
+/// assert_eq!(addr, "QXHFfTBKYXjaaTH1e7Rox8CcdNPGHVhM59");
+/// ```
+pub fn from_compressed_pubkey(
+    pubkey: &[u8; 33],
+    network: QtumNetwork,
+) -> Result<Base58Address, QtumAddressError> {
+    if pubkey[0] != 0x02 && pubkey[0] != 0x03 {
+        return Err(QtumAddressError::InvalidPublicKey);
+    }
+
+    from_pubkey(pubkey, network)
+}
+
+/// Derives the pubkey hash address of a 65-byte uncompressed secp256k1 public key, the same
+/// way as `from_compressed_pubkey`.
+///
+/// Keys not starting with `0x04`, or not on the curve, give `InvalidPublicKey`. The address
+/// differs from the one of the compressed form of the same key.
+pub fn from_uncompressed_pubkey(
+    pubkey: &[u8; 65],
+    network: QtumNetwork,
+) -> Result<Base58Address, QtumAddressError> {
+    if pubkey[0] != 0x04 {
+        return Err(QtumAddressError::InvalidPublicKey);
+    }
+
+    from_pubkey(pubkey, network)
+}
+
+/// Hashing a SEC1 encoded public key after checking that it is on the curve
+pub(crate) fn from_pubkey(
+    pubkey: &[u8],
+    network: QtumNetwork,
+) -> Result<Base58Address, QtumAddressError> {
+    PublicKey::from_sec1_bytes(pubkey).map_err(|_| QtumAddressError::InvalidPublicKey)?;
+
+    Ok(QtumAddress::new(network).from_hash160(&hash160(pubkey)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// Public key of the private key 1, the secp256k1 generator
+    const GENERATOR: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    /// Uncompressed form of `GENERATOR`
+    const GENERATOR_UNCOMPRESSED: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    fn pubkey(hex: &str) -> [u8; 33] {
+        let mut pubkey = [0u8; 33];
+        hex::decode_to_slice(hex, &mut pubkey).unwrap();
+        pubkey
+    }
+
+    fn uncompressed(hex: &str) -> [u8; 65] {
+        let mut pubkey = [0u8; 65];
+        hex::decode_to_slice(hex, &mut pubkey).unwrap();
+        pubkey
+    }
+
+    /// WIF of the private key 1 on mainnet, which shares its WIF prefix with bitcoin
+    const WIF_COMPRESSED: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+    const WIF_UNCOMPRESSED: &str = "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf";
+
+    #[test]
+    fn wif_addresses() {
+        assert_eq!(
+            from_wif(WIF_COMPRESSED, QtumNetwork::Mainnet).unwrap(),
+            from_compressed_pubkey(&pubkey(GENERATOR), QtumNetwork::Mainnet).unwrap()
+        );
+        assert_eq!(
+            from_wif(WIF_UNCOMPRESSED, QtumNetwork::Mainnet).unwrap(),
+            "QZtMgPgmVSujMWKykPTbtVqgE1URC3ibGT"
+        );
+        assert_eq!(
+            from_wif(
+                "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA",
+                QtumNetwork::Testnet
+            )
+            .unwrap(),
+            "qUEeiBfBZiTuHKvPA85a1u5PeeMkLnNF3K"
+        );
+        assert_eq!(
+            from_wif(
+                "91avARGdfge8E4tZfYLoxeJ5sGBdNJQH4kvjJoQFacbgwmaKkrx",
+                QtumNetwork::Regtest
+            )
+            .unwrap(),
+            "qWqkj8AdWde44NyMGQ7MxGiTFHSuG8cC24"
+        );
+    }
+
+    #[test]
+    fn invalid_wifs() {
+        assert_eq!(
+            from_wif(WIF_COMPRESSED, QtumNetwork::Testnet),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0xef,
+                found: 0x80
+            })
+        );
+        assert_eq!(
+            from_wif(WIF_COMPRESSED, QtumNetwork::Custom(0x1f)),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x1f })
+        );
+        assert_eq!(
+            from_wif(
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWo",
+                QtumNetwork::Mainnet
+            ),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        // Compression flag 0x02
+        assert_eq!(
+            from_wif(
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sfZr2ym",
+                QtumNetwork::Mainnet
+            ),
+            Err(QtumAddressError::InvalidPrivateKey)
+        );
+        // Private key 0
+        assert_eq!(
+            from_wif(
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73Nd2Mcv1",
+                QtumNetwork::Mainnet
+            ),
+            Err(QtumAddressError::InvalidPrivateKey)
+        );
+        assert_eq!(
+            from_wif("QXHFfTBKYXjaaTH1e7Rox8CcdNPGHVhM59", QtumNetwork::Mainnet),
+            Err(QtumAddressError::InvalidLength {
+                expected: 37,
+                found: 25
+            })
+        );
+        assert_eq!(
+            from_wif("", QtumNetwork::Mainnet),
+            Err(QtumAddressError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn uncompressed_pubkey_addresses() {
+        // Same hash160 as bitcoin's 1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm
+        let key = uncompressed(GENERATOR_UNCOMPRESSED);
+
+        let mainnet = from_uncompressed_pubkey(&key, QtumNetwork::Mainnet).unwrap();
+        assert_eq!(mainnet, "QZtMgPgmVSujMWKykPTbtVqgE1URC3ibGT");
+        assert_eq!(
+            QtumAddress::mainnet().gethexaddress(&mainnet).unwrap(),
+            "91b24bf9f5288532960ac687abb035127b1d28a5"
+        );
+        assert_eq!(
+            from_uncompressed_pubkey(&key, QtumNetwork::Testnet).unwrap(),
+            "qWqkj8AdWde44NyMGQ7MxGiTFHSuG8cC24"
+        );
+        assert_ne!(
+            mainnet,
+            from_compressed_pubkey(&pubkey(GENERATOR), QtumNetwork::Mainnet).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_uncompressed_pubkeys() {
+        let mut key = uncompressed(GENERATOR_UNCOMPRESSED);
+
+        key[0] = 0x02;
+        assert_eq!(
+            from_uncompressed_pubkey(&key, QtumNetwork::Mainnet),
+            Err(QtumAddressError::InvalidPublicKey)
+        );
+
+        key[0] = 0x04;
+        key[64] ^= 0x01;
+        assert_eq!(
+            from_uncompressed_pubkey(&key, QtumNetwork::Mainnet),
+            Err(QtumAddressError::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn compressed_pubkey_addresses() {
+        let key = pubkey(GENERATOR);
+
+        assert_eq!(
+            from_compressed_pubkey(&key, QtumNetwork::Mainnet).unwrap(),
+            "QXHFfTBKYXjaaTH1e7Rox8CcdNPGHVhM59"
+        );
+
+        let testnet = from_compressed_pubkey(&key, QtumNetwork::Testnet).unwrap();
+        assert_eq!(testnet, "qUEeiBfBZiTuHKvPA85a1u5PeeMkLnNF3K");
+        assert_eq!(
+            QtumAddress::testnet().gethexaddress(&testnet).unwrap(),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn invalid_compressed_pubkeys() {
+        let mut key = pubkey(GENERATOR);
+
+        key[0] = 0x04;
+        assert_eq!(
+            from_compressed_pubkey(&key, QtumNetwork::Mainnet),
+            Err(QtumAddressError::InvalidPublicKey)
+        );
+
+        // x = 5 is not the x coordinate of any point on secp256k1
+        let mut off_curve = [0u8; 33];
+        off_curve[0] = 0x02;
+        off_curve[32] = 0x05;
+        assert_eq!(
+            from_compressed_pubkey(&off_curve, QtumNetwork::Mainnet),
+            Err(QtumAddressError::InvalidPublicKey)
+        );
+    }
+}
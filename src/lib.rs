@@ -14,114 +14,932 @@
 //! assert_eq!(addr, qtum_addr)
 //! ```
 //!
-use basex_rs::{BaseX, Decode, Encode, BITCOIN};
-use bitcoin_hashes::sha256;
-use bitcoin_hashes::Hash;
-use hex;
+//! Without the default `std` feature the crate is `no_std` and only needs `alloc`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+// Tests use the collections and clock of `std` with or without the feature
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base58::BITCOIN;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+pub(crate) use hashes::hash160;
+use hashes::sha256d;
+
+mod abi;
+mod address;
+mod base58;
+mod batch;
+mod bip21;
+mod builder;
+#[cfg(feature = "contract")]
+mod contract;
+mod convert;
+#[cfg(feature = "eip55")]
+mod eip55;
+mod electrum;
+mod error;
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod hashes;
+mod inspect;
+#[cfg(feature = "key_derivation")]
+mod key;
+#[doc(hidden)]
+pub mod literal;
+mod params;
+pub mod prelude;
+mod sanitize;
+mod script;
+mod segwit;
+mod tx;
+mod types;
+mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm_bindings;
+
+pub use abi::{abi_decode_address, abi_encode_address, ABI_WORD_LEN};
+pub use address::{classify_hex_address, detect_address_type, Address, AddressType};
+pub use bip21::{from_bip21_uri, to_bip21_uri, PaymentRequest};
+pub use builder::QtumAddressBuilder;
+#[cfg(feature = "contract")]
+pub use contract::{
+    contract_address_create, contract_address_create2, contract_address_from_outpoint,
+};
+pub use convert::{convert_network, Converted, InputKind};
+#[cfg(feature = "eip55")]
+pub use eip55::{to_eip55, verify_eip55};
+pub use error::{ParseNetworkError, QtumAddressError};
+pub use ext::{ToBase58Address, ToHexAddress};
+pub use inspect::AddressInfo;
+#[cfg(feature = "key_derivation")]
+pub use key::{from_compressed_pubkey, from_uncompressed_pubkey, from_wif};
+pub use params::NetworkParams;
+pub use script::{MultisigInfo, ParsedScript, ScriptKind, MAX_REDEEM_SCRIPT_LEN};
+pub use segwit::{WitnessProgram, MAX_BECH32_LEN, MAX_WITNESS_VERSION, WSH_PROGRAM_LEN};
+pub use tx::{TxAddress, TxOutAddress};
+pub use types::{sort_addresses, Base58Address, HexAddress, HexFormat, SHORT_DEFAULT_LEN};
+pub use validation::{
+    is_valid_base58_address, is_valid_hex_address, normalize_hex_address, ValidationReport,
+};
+
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+pub struct ReadmeDoctests;
+
+/// Version byte of mainnet pubkey hash addresses
+pub const MAINNET_PREFIX: u8 = 0x3a;
+
+/// Version byte of testnet (and regtest) pubkey hash addresses
+pub const TESTNET_PREFIX: u8 = 0x78;
+
+/// Version byte of regtest pubkey hash addresses, the same as testnet's
+pub const REGTEST_PREFIX: u8 = TESTNET_PREFIX;
+
+/// Length of the hash160 carried by an address
+pub const ADDRESS_PAYLOAD_LEN: usize = 20;
+
+/// Length of a hex address without the `0x` prefix
+pub const HEX_ADDRESS_LEN: usize = 2 * ADDRESS_PAYLOAD_LEN;
+
+/// Length of the base58check checksum
+pub const CHECKSUM_LEN: usize = 4;
+
+/// Version byte followed by the hash160
+pub(crate) const VERSIONED_PAYLOAD_LEN: usize = 1 + ADDRESS_PAYLOAD_LEN;
+
+/// Decoded base58 address: versioned payload followed by the checksum
+pub(crate) const RAW_ADDRESS_LEN: usize = VERSIONED_PAYLOAD_LEN + CHECKSUM_LEN;
+
+/// Maximum length of a base58 address.
+///
+/// 25 bytes (version, 20-byte hash, checksum) never encode to more than 35 base58 characters,
+/// longer inputs are rejected before decoding.
+pub const MAX_BASE58_ADDRESS_LEN: usize = 35;
+
+/// Maximum length of the input of `decode_base58check` and `decode_base58check_prefixed`.
+///
+/// Base58 decoding is quadratic in the input length, so longer inputs are rejected before
+/// decoding. 128 characters leave room for 111-character BIP32 extended keys.
+pub const MAX_BASE58CHECK_LEN: usize = 128;
+
+/// Maximum length of a hex address: 40 hex characters plus the optional `0x` prefix
+pub const MAX_HEX_ADDRESS_LEN: usize = HEX_ADDRESS_LEN + 2;
+
+/// Converter for mainnet pubkey hash addresses, usable without any setup
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let eth_addr = TESTNET.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(eth_addr, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+/// assert!(MAINNET.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").is_err());
+/// ```
+pub const MAINNET: QtumAddress = QtumAddress::mainnet();
+
+/// Converter for testnet pubkey hash addresses, usable without any setup
+pub const TESTNET: QtumAddress = QtumAddress::testnet();
 
 /// Enum of Qtum networks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum QtumNetwork {
     /// Prefix address - 0x3a
     Mainnet,
     /// Prefix address - 0x78
     Testnet,
+    /// Prefix address - 0x78, the same byte as testnet as in Qtum Core chainparams.
+    ///
+    /// Regtest addresses cannot be told apart from testnet ones, so prefix byte lookups
+    /// always report `Testnet`.
+    Regtest,
+    /// Any other prefix byte, for forks and private chains
+    Custom(u8),
 }
 
 impl QtumNetwork {
     /// Getting prefix byte from network type
-    pub fn to_prefix_byte(&self) -> u8 {
+    pub const fn to_prefix_byte(&self) -> u8 {
+        match self.params_or_prefix() {
+            Ok(params) => params.pubkey_address,
+            Err(prefix) => prefix,
+        }
+    }
+
+    /// Getting the pay to script hash prefix byte of the network.
+    ///
+    /// Mainnet uses `0x32`, testnet and regtest `0x6e`, a `Custom` network uses its byte for
+    /// both kinds of address.
+    pub const fn to_script_prefix_byte(&self) -> u8 {
+        match self.params_or_prefix() {
+            Ok(params) => params.script_address,
+            Err(prefix) => prefix,
+        }
+    }
+
+    /// Getting network type from prefix byte, `None` for prefixes of no known network
+    ///
+    /// `0x78` is reported as `Testnet`, even for addresses generated on regtest.
+    pub fn from_prefix_byte(prefix: u8) -> Option<Self> {
+        match prefix {
+            MAINNET_PREFIX => Some(QtumNetwork::Mainnet),
+            TESTNET_PREFIX => Some(QtumNetwork::Testnet),
+            _ => None,
+        }
+    }
+
+    /// Network of a base58 address, same as `detect_network`
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let network = QtumNetwork::from_address("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+    /// assert!(network.is_testnet());
+    /// ```
+    pub fn from_address(address: &str) -> Result<Self, QtumAddressError> {
+        detect_network(address)
+    }
+
+    /// Whether this is the Qtum mainnet
+    pub const fn is_mainnet(&self) -> bool {
+        matches!(self, QtumNetwork::Mainnet)
+    }
+
+    /// Whether this is the Qtum testnet, regtest is not included
+    pub const fn is_testnet(&self) -> bool {
+        matches!(self, QtumNetwork::Testnet)
+    }
+
+    /// All named networks, `Custom` is left out
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let names: Vec<&str> = QtumNetwork::all().iter().map(|network| network.name()).collect();
+    /// assert_eq!(names, ["mainnet", "testnet", "regtest"]);
+    /// ```
+    pub const fn all() -> &'static [QtumNetwork] {
+        &[
+            QtumNetwork::Mainnet,
+            QtumNetwork::Testnet,
+            QtumNetwork::Regtest,
+        ]
+    }
+
+    /// Human readable part of native segwit addresses, `None` for `Custom` networks.
+    ///
+    /// This is where regtest differs from testnet: Qtum Core uses `qcrt` instead of `tq`.
+    pub const fn bech32_hrp(&self) -> Option<&'static str> {
+        match self.params() {
+            Some(params) => Some(params.bech32_hrp),
+            None => None,
+        }
+    }
+
+    /// Same as `name`
+    pub const fn as_str(&self) -> &'static str {
+        self.name()
+    }
+
+    /// Lowercase name of the network, `"custom"` for every `Custom` network
+    pub const fn name(&self) -> &'static str {
+        match self {
+            QtumNetwork::Mainnet => "mainnet",
+            QtumNetwork::Testnet => "testnet",
+            QtumNetwork::Regtest => "regtest",
+            QtumNetwork::Custom(_) => "custom",
+        }
+    }
+}
+
+impl From<QtumNetwork> for u8 {
+    fn from(network: QtumNetwork) -> Self {
+        network.to_prefix_byte()
+    }
+}
+
+impl From<QtumNetwork> for &'static str {
+    fn from(network: QtumNetwork) -> Self {
+        network.name()
+    }
+}
+
+/// Lowercase network name as in `name`, custom networks also show their prefix byte
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// assert_eq!(QtumNetwork::Testnet.to_string(), "testnet");
+/// assert_eq!(QtumNetwork::Custom(0x1f).to_string(), "custom(0x1f)");
+/// ```
+impl fmt::Display for QtumNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            QtumNetwork::Mainnet => 0x3a,
-            QtumNetwork::Testnet => 0x78,
+            QtumNetwork::Custom(prefix) => write!(f, "custom({:#04x})", prefix),
+            network => f.write_str(network.name()),
         }
     }
 }
 
-impl From<u8> for QtumNetwork {
-    fn from(item: u8) -> Self {
-        match item {
-            0x3a => QtumNetwork::Mainnet,
-            0x78 => QtumNetwork::Testnet,
-            _ => panic!(""),
+/// Parsing a network name as found in config files and environment variables,
+/// `mainnet`, `testnet` and `regtest` or the short `main` and `test`, in any case.
+/// The `custom(0x1f)` form written by `Display` is accepted as well.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let network: QtumNetwork = "Testnet".parse().unwrap();
+/// assert_eq!(network, QtumNetwork::Testnet);
+/// assert!("signet".parse::<QtumNetwork>().is_err());
+/// ```
+impl FromStr for QtumNetwork {
+    type Err = ParseNetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+
+        match name.as_str() {
+            "mainnet" | "main" => Ok(QtumNetwork::Mainnet),
+            "testnet" | "test" => Ok(QtumNetwork::Testnet),
+            "regtest" => Ok(QtumNetwork::Regtest),
+            _ => name
+                .strip_prefix("custom(0x")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .filter(|byte| byte.len() == 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .map(QtumNetwork::Custom)
+                .ok_or_else(|| ParseNetworkError {
+                    name: s.to_string(),
+                }),
         }
     }
 }
 
+/// Conversion from a prefix byte, bytes of no known network become `Custom`
+///
+/// ```rust
+/// use qtum_address_rust::*;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(QtumNetwork::try_from(0x3a), Ok(QtumNetwork::Mainnet));
+/// assert_eq!(QtumNetwork::try_from(0x1f), Ok(QtumNetwork::Custom(0x1f)));
+/// ```
+impl TryFrom<u8> for QtumNetwork {
+    type Error = QtumAddressError;
+
+    fn try_from(item: u8) -> Result<Self, Self::Error> {
+        Ok(QtumNetwork::from_prefix_byte(item).unwrap_or(QtumNetwork::Custom(item)))
+    }
+}
+
 /// Structure for conversion ktum addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct QtumAddress {
+    network: QtumNetwork,
+    kind: AddressType,
     prefix: u8,
+    verify_checksum: bool,
+    allow_0x_prefix: bool,
+    hex_format: HexFormat,
 }
 
 impl QtumAddress {
     /// Initialization of the address conversion structure
-    pub fn new(network: QtumNetwork) -> Self {
-        Self {
-            prefix: network.to_prefix_byte(),
-        }
+    pub const fn new(network: QtumNetwork) -> Self {
+        QtumAddress::builder().network(network).build()
+    }
+
+    /// Initialization of a conversion structure for pay to script hash addresses
+    pub const fn new_p2sh(network: QtumNetwork) -> Self {
+        QtumAddress::builder()
+            .network(network)
+            .address_type(AddressType::P2SH)
+            .build()
+    }
+
+    /// Conversion structure for mainnet pubkey hash addresses
+    pub const fn mainnet() -> Self {
+        QtumAddress::new(QtumNetwork::Mainnet)
+    }
+
+    /// Conversion structure for testnet pubkey hash addresses
+    pub const fn testnet() -> Self {
+        QtumAddress::new(QtumNetwork::Testnet)
+    }
+
+    /// Builder for a conversion structure with non-default options
+    pub const fn builder() -> QtumAddressBuilder {
+        QtumAddressBuilder::new()
+    }
+
+    /// Network the conversion structure was initialized with
+    pub const fn network(&self) -> QtumNetwork {
+        self.network
+    }
+
+    /// Kind of base58 address the conversion structure produces
+    pub const fn address_type(&self) -> AddressType {
+        self.kind
+    }
+
+    /// Setting the rendering of hex addresses, bare lowercase by default
+    pub fn with_hex_format(mut self, format: HexFormat) -> Self {
+        self.hex_format = format;
+        self
     }
 
     /// Converts a base58 pubkeyhash address to a hex address for use in smart contracts.
-    pub fn gethexaddress(&self, address: &str) -> Result<String, &str> {
-        if address.is_empty() {
-            return Err("Invalid address");
-        }
+    pub fn gethexaddress(&self, address: &str) -> Result<HexAddress, QtumAddressError> {
+        let hash = self.gethexaddress_raw(address)?;
 
-        let decode_bytes = match BaseX::new(BITCOIN).decode(address.to_string()) {
-            Some(bytes) => bytes,
-            None => return Err("Invalid address"),
-        };
+        Ok(self.hex_format.format(&hash))
+    }
 
-        let new_bytes = match decode_bytes.get(1..21) {
-            Some(hash) => hash,
-            None => return Err("Invalid address"),
-        };
+    /// Converts a base58 address of either kind of the configured network to a hex address,
+    /// reporting whether a pubkey hash or a script hash was decoded.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::new(QtumNetwork::Testnet);
+    /// let script = QtumAddress::new_p2sh(QtumNetwork::Testnet)
+    ///     .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+    ///     .unwrap();
+    ///
+    /// let (hex, kind) = qtum.gethexaddress_typed(&script).unwrap();
+    /// assert_eq!(hex, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+    /// assert_eq!(kind, AddressType::P2SH);
+    /// ```
+    pub fn gethexaddress_typed(
+        &self,
+        address: &str,
+    ) -> Result<(HexAddress, AddressType), QtumAddressError> {
+        let payload = decode_address_bytes(address, self.verify_checksum)?;
+
+        let kind = self
+            .kind_of_version(payload[0])
+            .ok_or(QtumAddressError::NetworkMismatch {
+                expected: self.prefix,
+                found: payload[0],
+            })?;
+
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&payload[1..VERSIONED_PAYLOAD_LEN]);
+
+        Ok((self.hex_format.format(&hash), kind))
+    }
 
-        let hex = hex::encode(new_bytes);
+    /// Converts a base58 address of any known network, reporting the network it belongs to.
+    ///
+    /// The checksum is always verified, pubkey hash and script hash addresses are accepted and
+    /// unknown version bytes give `UnknownNetwork`. Regtest addresses are reported as `Testnet`.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let (network, hex) = QtumAddress::gethexaddress_any("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+    /// assert_eq!(network, QtumNetwork::Testnet);
+    /// assert_eq!(hex, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+    /// ```
+    pub fn gethexaddress_any(address: &str) -> Result<(QtumNetwork, HexAddress), QtumAddressError> {
+        let payload = decode_address_bytes(address, true)?;
 
-        Ok(hex)
+        let (network, _) = address::classify_version(payload[0])
+            .ok_or(QtumAddressError::UnknownNetwork { prefix: payload[0] })?;
+
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&payload[1..VERSIONED_PAYLOAD_LEN]);
+
+        Ok((network, HexAddress::from(hash)))
+    }
+
+    /// Converts a base58 pubkeyhash address to the raw 20 bytes of the hex address,
+    /// with the same validation as `gethexaddress` but without allocating
+    pub fn gethexaddress_raw(
+        &self,
+        address: &str,
+    ) -> Result<[u8; ADDRESS_PAYLOAD_LEN], QtumAddressError> {
+        let payload = self.decode(address, self.verify_checksum)?;
+
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&payload[1..VERSIONED_PAYLOAD_LEN]);
+
+        Ok(hash)
+    }
+
+    /// Converts a base58 pubkeyhash address to the raw 20 bytes of the hex address
+    #[deprecated(since = "0.3.0", note = "renamed to `QtumAddress::gethexaddress_raw`")]
+    pub fn gethexaddress_bytes(
+        &self,
+        address: &str,
+    ) -> Result<[u8; ADDRESS_PAYLOAD_LEN], QtumAddressError> {
+        self.gethexaddress_raw(address)
+    }
+
+    /// Same as `gethexaddress`, but does not verify the base58check checksum.
+    ///
+    /// Only use this for addresses that are already known to be valid.
+    pub fn gethexaddress_unchecked(&self, address: &str) -> Result<HexAddress, QtumAddressError> {
+        let payload = self.decode(address, false)?;
+
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&payload[1..VERSIONED_PAYLOAD_LEN]);
+
+        Ok(self.hex_format.format(&hash))
     }
 
     /// Converts a raw hex address to a base58 pubkeyhash address
-    pub fn fromhexaddress(&self, address: &str) -> Result<String, &str> {
-        if address.is_empty() || address.len() != 40 {
-            return Err("Invalid address");
+    ///
+    /// The hex address is normalized like `normalize_hex_address` does: surrounding whitespace
+    /// is trimmed, any case is accepted and an optional `0x` prefix is stripped, unless the
+    /// prefix was disallowed with the builder.
+    pub fn fromhexaddress(&self, address: &str) -> Result<Base58Address, QtumAddressError> {
+        let hash = self.decode_hex(address)?;
+
+        Ok(self.encode_payload(&hash))
+    }
+
+    /// Converts the raw bytes of a hex address to a base58 pubkeyhash address,
+    /// the slice must be exactly 20 bytes long
+    pub fn fromhexaddress_bytes(&self, payload: &[u8]) -> Result<Base58Address, QtumAddressError> {
+        if payload.len() != ADDRESS_PAYLOAD_LEN {
+            return Err(QtumAddressError::InvalidLength {
+                expected: ADDRESS_PAYLOAD_LEN,
+                found: payload.len(),
+            });
         }
 
-        let mut address_bytes = match hex::decode(address) {
-            Ok(bytes) => bytes,
-            Err(_) => return Err("Invalid address"),
-        };
-        address_bytes.insert(0, self.prefix);
+        Ok(self.encode_payload(payload))
+    }
+
+    /// Builds a base58 script hash address of the configured network from a 20-byte script hash,
+    /// whatever kind of address the converter is configured for
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let script = QtumAddress::mainnet().from_script_hash(&[0x11; 20]);
+    /// assert!(script.starts_with('M'));
+    /// assert_eq!(detect_address_type(&script), Ok(AddressType::P2SH));
+    /// ```
+    pub fn from_script_hash(&self, script_hash: &[u8; ADDRESS_PAYLOAD_LEN]) -> Base58Address {
+        Base58Address(encode_base58check(
+            self.network.to_script_prefix_byte(),
+            script_hash,
+        ))
+    }
+
+    /// Builds a base58 pubkeyhash address of the configured network from a 20-byte hash160,
+    /// whatever kind of address the converter is configured for
+    pub fn from_hash160(&self, hash: &[u8; ADDRESS_PAYLOAD_LEN]) -> Base58Address {
+        Base58Address(encode_base58check(self.network.to_prefix_byte(), hash))
+    }
+
+    /// Base58 address of a 20-byte payload with the configured version byte
+    pub(crate) fn encode_payload(&self, payload: &[u8]) -> Base58Address {
+        Base58Address(encode_base58check(self.prefix, payload))
+    }
+
+    /// Converts a 20-byte payload, e.g. from a contract event log, to a base58 pubkeyhash address
+    /// without going through a hex string. The counterpart of `gethexaddress_raw`, never fails
+    /// and returns a `Result` to match `fromhexaddress`.
+    pub fn fromhexaddress_raw(
+        &self,
+        payload: &[u8; ADDRESS_PAYLOAD_LEN],
+    ) -> Result<Base58Address, QtumAddressError> {
+        Ok(self.encode_payload(payload))
+    }
+
+    /// Suggests a correction for an address with a single mistyped character.
+    ///
+    /// Every position is substituted with every base58 character, the correction is returned
+    /// only if exactly one candidate passes the checksum and belongs to the configured network.
+    /// Valid addresses, inputs longer than `MAX_BASE58_ADDRESS_LEN` and ambiguous typos give `None`.
+    pub fn suggest_correction(&self, address: &str) -> Option<Base58Address> {
+        if address.len() > MAX_BASE58_ADDRESS_LEN || self.decode(address, true).is_ok() {
+            return None;
+        }
+
+        let chars: Vec<char> = address.chars().collect();
+        let mut suggestion = None;
 
-        let checksum = self.hash(&self.hash(&address_bytes));
-        match checksum.get(0..4) {
-            Some(hash) => {
-                for byte in hash.iter() {
-                    address_bytes.push(*byte);
+        for position in 0..chars.len() {
+            for &c in BITCOIN.iter() {
+                let c = c as char;
+                if chars[position] == c {
+                    continue;
                 }
-            }
-            None => return Err("Invalid address"),
-        };
 
-        let encode = BaseX::new(BITCOIN).encode(&address_bytes);
+                let mut candidate = chars.clone();
+                candidate[position] = c;
+                let candidate: String = candidate.into_iter().collect();
+
+                if self.decode(&candidate, true).is_ok() {
+                    if suggestion.is_some() {
+                        return None;
+                    }
+                    suggestion = Some(Base58Address(candidate));
+                }
+            }
+        }
 
-        Ok(encode)
+        suggestion
     }
 
-    /// Adding an ethereum address prefix
+    /// Adding an ethereum address prefix, already prefixed addresses are returned unchanged
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `QtumAddress::with_hex_prefix`, which validates the address"
+    )]
     pub fn addprefix(address: &str) -> String {
-        format!("0x{}", address)
+        format!("0x{}", QtumAddress::removeprefix(address))
+    }
+
+    /// Adding the `0x` prefix to a 40 character hex address, already prefixed addresses
+    /// are returned unchanged
+    pub fn with_hex_prefix(address: &str) -> Result<String, QtumAddressError> {
+        Ok(format!("0x{}", QtumAddress::strip_hex_prefix(address)?))
+    }
+
+    /// Removing the `0x` or `0X` prefix if present, what remains must be 40 hex characters
+    pub fn strip_hex_prefix(address: &str) -> Result<&str, QtumAddressError> {
+        let stripped = QtumAddress::removeprefix(address);
+
+        if stripped.len() != HEX_ADDRESS_LEN {
+            return Err(QtumAddressError::InvalidLength {
+                expected: HEX_ADDRESS_LEN,
+                found: stripped.len(),
+            });
+        }
+
+        if !stripped.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(QtumAddressError::InvalidHex);
+        }
+
+        Ok(stripped)
+    }
+
+    /// Removing an ethereum address prefix (`0x` or `0X`) if present
+    pub fn removeprefix(address: &str) -> &str {
+        address
+            .strip_prefix("0x")
+            .or_else(|| address.strip_prefix("0X"))
+            .unwrap_or(address)
+    }
+
+    /// Error for a version byte other than the configured one: `WrongAddressType` for
+    /// the other kind of address of the same network, `NetworkMismatch` otherwise
+    pub(crate) fn version_error(&self, version: u8) -> QtumAddressError {
+        match self.kind_of_version(version) {
+            Some(found) if found != self.kind => QtumAddressError::WrongAddressType {
+                expected: self.kind,
+                found,
+            },
+            _ => QtumAddressError::NetworkMismatch {
+                expected: self.prefix,
+                found: version,
+            },
+        }
     }
 
-    /// SHA256 hash function
-    fn hash(&self, byte: &Vec<u8>) -> Vec<u8> {
-        hex::decode(sha256::Hash::hash(byte.as_slice()).to_string()).unwrap()
+    /// Kind of address a version byte stands for on the configured network
+    pub(crate) fn kind_of_version(&self, version: u8) -> Option<AddressType> {
+        if version == self.prefix {
+            Some(self.kind)
+        } else if version == self.network.to_prefix_byte() {
+            Some(AddressType::P2PKH)
+        } else if version == self.network.to_script_prefix_byte() {
+            Some(AddressType::P2SH)
+        } else {
+            None
+        }
+    }
+
+    /// Decoding a hex address with the configured prefix rule
+    pub(crate) fn decode_hex(
+        &self,
+        address: &str,
+    ) -> Result<[u8; ADDRESS_PAYLOAD_LEN], QtumAddressError> {
+        let address = address.trim();
+        if !self.allow_0x_prefix && QtumAddress::removeprefix(address).len() != address.len() {
+            return Err(QtumAddressError::InvalidHex);
+        }
+
+        decode_hex(address)
+    }
+
+    /// Decoding a base58check address of the configured network
+    fn decode(&self, address: &str, verify_checksum: bool) -> Result<Vec<u8>, QtumAddressError> {
+        let decode_bytes = decode_address_bytes(address, verify_checksum)?;
+
+        if decode_bytes[0] != self.prefix {
+            return Err(self.version_error(decode_bytes[0]));
+        }
+
+        Ok(decode_bytes)
+    }
+}
+
+/// Converts a base58 pubkeyhash address of the given network to a hex address,
+/// same as `QtumAddress::new(network).gethexaddress(address)`
+///
+/// ```rust
+/// use qtum_address_rust::prelude::*;
+///
+/// let eth_addr = to_hex("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", QtumNetwork::Testnet).unwrap();
+/// assert_eq!(eth_addr, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+///
+/// assert!(to_hex("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu", QtumNetwork::Testnet).is_err());
+/// assert!(to_hex("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", QtumNetwork::Mainnet).is_err());
+/// ```
+pub fn to_hex(address: &str, network: QtumNetwork) -> Result<HexAddress, QtumAddressError> {
+    QtumAddress::new(network).gethexaddress(address)
+}
+
+/// Converts a hex address to a base58 pubkeyhash address of the given network,
+/// same as `QtumAddress::new(network).fromhexaddress(hex)`
+///
+/// ```rust
+/// use qtum_address_rust::prelude::*;
+///
+/// let qtum_addr = to_base58("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68", QtumNetwork::Testnet);
+/// assert_eq!(qtum_addr.unwrap(), "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+///
+/// assert!(to_base58("0xhello", QtumNetwork::Testnet).is_err());
+/// ```
+pub fn to_base58(hex: &str, network: QtumNetwork) -> Result<Base58Address, QtumAddressError> {
+    QtumAddress::new(network).fromhexaddress(hex)
+}
+
+/// Detects the network of a base58 address from its version byte.
+///
+/// The checksum is verified, pubkey hash and script hash version bytes are recognized.
+/// Version bytes of no known network give `UnknownNetwork`, regtest addresses are reported
+/// as `Testnet` since both share the same bytes.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let network = detect_network("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(network, QtumNetwork::Testnet);
+/// ```
+pub fn detect_network(address: &str) -> Result<QtumNetwork, QtumAddressError> {
+    let payload = decode_address_bytes(address, true)?;
+
+    address::classify_version(payload[0])
+        .map(|(network, _)| network)
+        .ok_or(QtumAddressError::UnknownNetwork { prefix: payload[0] })
+}
+
+/// All networks a base58 address can belong to, judging by its version byte.
+///
+/// Testnet and regtest share their base58 version bytes, so their addresses give both
+/// networks and only the context can tell them apart. Errors are the same as for `detect_network`.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let networks = detect_networks("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(networks, [QtumNetwork::Testnet, QtumNetwork::Regtest]);
+/// ```
+pub fn detect_networks(address: &str) -> Result<&'static [QtumNetwork], QtumAddressError> {
+    match detect_network(address)? {
+        QtumNetwork::Mainnet => Ok(&[QtumNetwork::Mainnet]),
+        _ => Ok(&[QtumNetwork::Testnet, QtumNetwork::Regtest]),
+    }
+}
+
+/// The default converter is for **mainnet** pubkey hash addresses, the same as
+/// `QtumAddress::mainnet()`. Pick the network explicitly when handling testnet or regtest data.
+impl Default for QtumAddress {
+    fn default() -> Self {
+        QtumAddress::mainnet()
+    }
+}
+
+/// Base58check encoding of a version byte and a payload of any length, the encoding of
+/// addresses, WIF keys and other version prefixed data.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let hash = hex::decode("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68").unwrap();
+/// assert_eq!(encode_base58check(0x78, &hash), "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+/// ```
+pub fn encode_base58check(version: u8, payload: &[u8]) -> String {
+    encode_base58check_prefixed(&[version], payload)
+}
+
+/// Base58check encoding with a prefix of several bytes, such as the 4-byte version of
+/// BIP32 extended keys
+pub fn encode_base58check_prefixed(prefix: &[u8], payload: &[u8]) -> String {
+    let mut bytes = Vec::with_capacity(prefix.len() + payload.len() + CHECKSUM_LEN);
+    bytes.extend_from_slice(prefix);
+    bytes.extend_from_slice(payload);
+
+    let data_checksum = checksum(&bytes);
+    bytes.extend_from_slice(&data_checksum);
+
+    base58::encode(&bytes)
+}
+
+/// Decoding base58check data into its version byte and payload, the inverse of
+/// `encode_base58check`.
+///
+/// The checksum is always verified. The payload may have any length, so unlike the address
+/// functions there is no length check beyond the version byte and checksum being present.
+/// Inputs longer than `MAX_BASE58CHECK_LEN` characters give `TooLong`.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let (version, payload) = decode_base58check("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(version, 0x78);
+/// assert_eq!(hex::encode(payload), "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+/// ```
+pub fn decode_base58check(input: &str) -> Result<(u8, Vec<u8>), QtumAddressError> {
+    let (prefix, payload) = decode_base58check_prefixed(input, 1)?;
+
+    Ok((prefix[0], payload))
+}
+
+/// Decoding base58check data with a prefix of `prefix_len` bytes into prefix and payload,
+/// the inverse of `encode_base58check_prefixed`. Inputs are limited to `MAX_BASE58CHECK_LEN`
+/// characters like those of `decode_base58check`.
+pub fn decode_base58check_prefixed(
+    input: &str,
+    prefix_len: usize,
+) -> Result<(Vec<u8>, Vec<u8>), QtumAddressError> {
+    let bytes = decode_base58_input(input, MAX_BASE58CHECK_LEN)?;
+    if bytes.len() < prefix_len + CHECKSUM_LEN {
+        return Err(QtumAddressError::InvalidLength {
+            expected: prefix_len + CHECKSUM_LEN,
+            found: bytes.len(),
+        });
+    }
+
+    let mut prefix = split_checksum(bytes, true)?;
+    let payload = prefix.split_off(prefix_len);
+
+    Ok((prefix, payload))
+}
+
+/// Decoding a base58check address into the version byte and the payload
+pub(crate) fn decode_address_bytes(
+    address: &str,
+    verify_checksum: bool,
+) -> Result<Vec<u8>, QtumAddressError> {
+    split_checksum(decode_base58(address)?, verify_checksum)
+}
+
+/// Removing the trailing checksum of decoded base58check data, the caller has checked that
+/// there is one
+pub(crate) fn split_checksum(
+    mut bytes: Vec<u8>,
+    verify_checksum: bool,
+) -> Result<Vec<u8>, QtumAddressError> {
+    let data_checksum = bytes.split_off(bytes.len() - CHECKSUM_LEN);
+    if verify_checksum && checksum(&bytes)[..] != data_checksum[..] {
+        return Err(QtumAddressError::InvalidChecksum);
+    }
+
+    Ok(bytes)
+}
+
+/// Decoding a base58 address into its 25 raw bytes, the checksum is not verified
+pub(crate) fn decode_base58(address: &str) -> Result<Vec<u8>, QtumAddressError> {
+    let decode_bytes = decode_base58_input(address, MAX_BASE58_ADDRESS_LEN)?;
+
+    if decode_bytes.len() != RAW_ADDRESS_LEN {
+        return Err(QtumAddressError::InvalidLength {
+            expected: RAW_ADDRESS_LEN,
+            found: decode_bytes.len(),
+        });
+    }
+
+    Ok(decode_bytes)
+}
+
+/// Decoding base58 user input of at most `max_len` characters, with diagnostics for empty
+/// input, whitespace, homoglyphs and characters outside the alphabet
+pub(crate) fn decode_base58_input(
+    input: &str,
+    max_len: usize,
+) -> Result<Vec<u8>, QtumAddressError> {
+    if input.is_empty() {
+        return Err(QtumAddressError::EmptyInput);
+    }
+
+    sanitize::check_characters(input, max_len.saturating_add(1))?;
+
+    if input.len() > max_len {
+        return Err(QtumAddressError::TooLong {
+            max: max_len,
+            found: input.len(),
+        });
+    }
+
+    if let Some((position, character)) = input
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii() || !BITCOIN.contains(&(*c as u8)))
+    {
+        return Err(QtumAddressError::InvalidCharacter {
+            position,
+            character,
+        });
+    }
+
+    base58::decode(input).ok_or(QtumAddressError::InvalidBase58)
+}
+
+/// Decoding a 40 character hex address with an optional `0x` prefix, surrounding whitespace
+/// is ignored
+pub(crate) fn decode_hex(address: &str) -> Result<[u8; ADDRESS_PAYLOAD_LEN], QtumAddressError> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err(QtumAddressError::EmptyInput);
+    }
+
+    sanitize::check_characters(address, MAX_HEX_ADDRESS_LEN + 1)?;
+
+    if address.len() > MAX_HEX_ADDRESS_LEN {
+        return Err(QtumAddressError::TooLong {
+            max: MAX_HEX_ADDRESS_LEN,
+            found: address.len(),
+        });
+    }
+
+    let address = QtumAddress::strip_hex_prefix(address)?;
+
+    let mut bytes = [0u8; ADDRESS_PAYLOAD_LEN];
+    match hex::decode_to_slice(address, &mut bytes) {
+        Ok(()) => Ok(bytes),
+        Err(_) => Err(QtumAddressError::InvalidHex),
     }
 }
 
+/// Base58check checksum: first four bytes of double SHA256
+pub(crate) fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = sha256d(payload);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn it_works_testnet() {
@@ -161,4 +979,1294 @@ mod tests {
             assert_eq!(eth_addr.to_string(), addr.to_string());
         }
     }
+
+    #[test]
+    fn network_prefix_round_trip() {
+        for network in [QtumNetwork::Mainnet, QtumNetwork::Testnet].iter() {
+            let prefix = network.to_prefix_byte();
+
+            assert_eq!(QtumNetwork::from_prefix_byte(prefix), Some(*network));
+            assert_eq!(QtumNetwork::try_from(prefix), Ok(*network));
+        }
+    }
+
+    #[test]
+    fn network_try_from_unknown_byte() {
+        for byte in 0..=u8::MAX {
+            let network = QtumNetwork::try_from(byte).unwrap();
+            assert_eq!(network.to_prefix_byte(), byte);
+
+            match QtumNetwork::from_prefix_byte(byte) {
+                Some(known) => assert_eq!(network, known),
+                None => assert_eq!(network, QtumNetwork::Custom(byte)),
+            }
+        }
+    }
+
+    #[test]
+    fn custom_prefix_0x55_round_trip() {
+        let network = QtumNetwork::try_from(0x55).unwrap();
+        assert_eq!(network, QtumNetwork::Custom(0x55));
+        assert_eq!(network.to_prefix_byte(), 0x55);
+
+        for hex in &[
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+            "0000000000000000000000000000000000000000",
+            "ffffffffffffffffffffffffffffffffffffffff",
+        ] {
+            for qtum in &[QtumAddress::new(network), QtumAddress::new_p2sh(network)] {
+                let addr = qtum.fromhexaddress(hex).unwrap();
+                assert_eq!(qtum.gethexaddress(&addr).unwrap(), *hex);
+                assert_eq!(QtumAddress::inspect(&addr).unwrap().version, 0x55);
+                assert_eq!(
+                    QtumAddress::new(QtumNetwork::Custom(0x56)).gethexaddress(&addr),
+                    Err(QtumAddressError::NetworkMismatch {
+                        expected: 0x56,
+                        found: 0x55
+                    })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn leading_zero_bytes_round_trip() {
+        let hexes = [
+            "00a1a6ca2ae7c00b248bb2832d6f480f27da6800",
+            "0000a1a6ca2ae7c00b248bb2832d6f480f27da68",
+            "00000000000000000000000000000000000000ff",
+            "0000000000000000000000000000000000000000",
+        ];
+
+        for network in &[
+            QtumNetwork::Mainnet,
+            QtumNetwork::Testnet,
+            QtumNetwork::Custom(0x00),
+        ] {
+            for qtum in &[QtumAddress::new(*network), QtumAddress::new_p2sh(*network)] {
+                for hex in &hexes {
+                    let addr = qtum.fromhexaddress(hex).unwrap();
+                    assert_eq!(qtum.gethexaddress(&addr).unwrap(), *hex);
+                    assert_eq!(decode_base58(&addr).unwrap().len(), 25);
+                }
+            }
+        }
+
+        // A zero version byte is kept as a leading '1', like in bitcoin addresses
+        let zero = QtumAddress::new(QtumNetwork::Custom(0x00));
+        assert_eq!(
+            zero.fromhexaddress("0000000000000000000000000000000000000000")
+                .unwrap(),
+            "1111111111111111111114oLvT2"
+        );
+        let addr = zero
+            .fromhexaddress("0000a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap();
+        assert!(addr.starts_with("111"));
+    }
+
+    #[test]
+    fn custom_network_conversion() {
+        let qtum = QtumAddress::new(QtumNetwork::Custom(0x1f));
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+        let addr = qtum.fromhexaddress(hex).unwrap();
+        assert_eq!(
+            addr.as_str(),
+            base58check(&[&[0x1f][..], &hex::decode(hex).unwrap()].concat())
+        );
+        assert_eq!(qtum.gethexaddress(&addr).unwrap(), hex);
+
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Testnet).gethexaddress(&addr),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x78,
+                found: 0x1f
+            })
+        );
+
+        let name: &str = QtumNetwork::Custom(0x1f).into();
+        assert_eq!(name, "custom");
+    }
+
+    #[test]
+    fn network_into_u8_and_str() {
+        assert_eq!(u8::from(QtumNetwork::Mainnet), 0x3a);
+        assert_eq!(u8::from(QtumNetwork::Testnet), 0x78);
+
+        let name: &str = QtumNetwork::Mainnet.into();
+        assert_eq!(name, "mainnet");
+        let name: &str = QtumNetwork::Testnet.into();
+        assert_eq!(name, "testnet");
+        let name: &str = QtumNetwork::Regtest.into();
+        assert_eq!(name, "regtest");
+    }
+
+    #[test]
+    fn default_and_named_constructors() {
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        let mainnet = QtumAddress::new(QtumNetwork::Mainnet).fromhexaddress(hex);
+
+        assert_eq!(QtumAddress::default().fromhexaddress(hex), mainnet);
+        assert_eq!(QtumAddress::mainnet().fromhexaddress(hex), mainnet);
+        assert_eq!(
+            QtumAddress::testnet().fromhexaddress(hex).unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+    }
+
+    #[test]
+    fn configured_network_is_kept() {
+        for network in [
+            QtumNetwork::Mainnet,
+            QtumNetwork::Testnet,
+            QtumNetwork::Regtest,
+            QtumNetwork::Custom(0x1f),
+        ]
+        .iter()
+        {
+            assert_eq!(QtumAddress::new(*network).network(), *network);
+            assert_eq!(
+                QtumAddress::new(*network).address_type(),
+                AddressType::P2PKH
+            );
+            assert_eq!(
+                QtumAddress::new_p2sh(*network).address_type(),
+                AddressType::P2SH
+            );
+        }
+
+        assert_eq!(QtumAddress::default().network(), QtumNetwork::Mainnet);
+    }
+
+    #[test]
+    fn network_from_str() {
+        let cases = [
+            ("mainnet", QtumNetwork::Mainnet),
+            ("Mainnet", QtumNetwork::Mainnet),
+            ("MAINNET", QtumNetwork::Mainnet),
+            ("testnet", QtumNetwork::Testnet),
+            ("Testnet", QtumNetwork::Testnet),
+            ("TESTNET", QtumNetwork::Testnet),
+            ("regtest", QtumNetwork::Regtest),
+            ("Regtest", QtumNetwork::Regtest),
+            ("main", QtumNetwork::Mainnet),
+            ("TEST", QtumNetwork::Testnet),
+            ("custom(0x1f)", QtumNetwork::Custom(0x1f)),
+            ("Custom(0xFF)", QtumNetwork::Custom(0xff)),
+        ];
+
+        for (name, network) in cases.iter() {
+            assert_eq!(name.parse::<QtumNetwork>(), Ok(*network));
+        }
+
+        for name in [
+            "",
+            "custom",
+            " mainnet",
+            "testnet3",
+            "custom(0x1)",
+            "custom(0x100)",
+            "custom(31)",
+        ]
+        .iter()
+        {
+            let err = name.parse::<QtumNetwork>().unwrap_err();
+            assert_eq!(err.name(), *name);
+        }
+
+        let err = "signet".parse::<QtumNetwork>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown network \"signet\", expected one of mainnet, main, testnet, test, regtest or custom(0xNN)"
+        );
+        #[cfg(feature = "std")]
+        let _: &dyn std::error::Error = &err;
+    }
+
+    #[test]
+    fn length_constants_match_real_addresses() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let hex = qtum
+            .gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+            .unwrap();
+        assert_eq!(hex.len(), HEX_ADDRESS_LEN);
+        assert_eq!(
+            hex::decode(hex.as_str()).unwrap().len(),
+            ADDRESS_PAYLOAD_LEN
+        );
+
+        let raw = decode_base58("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+        assert_eq!(raw.len(), 1 + ADDRESS_PAYLOAD_LEN + CHECKSUM_LEN);
+        assert_eq!(raw[0], TESTNET_PREFIX);
+        assert_eq!(QtumNetwork::Mainnet.to_prefix_byte(), MAINNET_PREFIX);
+        assert_eq!(QtumNetwork::Regtest.to_prefix_byte(), REGTEST_PREFIX);
+    }
+
+    #[test]
+    fn all_networks_round_trip_by_name() {
+        assert_eq!(QtumNetwork::all().len(), 3);
+
+        for network in QtumNetwork::all() {
+            assert_eq!(network.name().parse::<QtumNetwork>(), Ok(*network));
+            let name: &str = (*network).into();
+            assert_eq!(name, network.name());
+        }
+
+        assert_eq!(QtumNetwork::Custom(0x1f).name(), "custom");
+    }
+
+    #[test]
+    fn network_display() {
+        assert_eq!(format!("{}", QtumNetwork::Mainnet), "mainnet");
+        assert_eq!(format!("{}", QtumNetwork::Testnet), "testnet");
+        assert_eq!(format!("{}", QtumNetwork::Regtest), "regtest");
+        assert_eq!(format!("{}", QtumNetwork::Custom(0x05)), "custom(0x05)");
+
+        for network in QtumNetwork::all()
+            .iter()
+            .chain(&[QtumNetwork::Custom(0x00), QtumNetwork::Custom(0xab)])
+        {
+            assert_eq!(network.to_string().parse::<QtumNetwork>(), Ok(*network));
+            assert_eq!(network.as_str(), network.name());
+        }
+    }
+
+    #[test]
+    fn const_converters() {
+        assert_eq!(MAINNET, QtumAddress::new(QtumNetwork::Mainnet));
+        assert_eq!(TESTNET, QtumAddress::new(QtumNetwork::Testnet));
+        assert_eq!(MAINNET.network(), QtumNetwork::Mainnet);
+        assert_eq!(TESTNET.network(), QtumNetwork::Testnet);
+
+        let hex = TESTNET
+            .gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+            .unwrap();
+        assert_eq!(
+            MAINNET.fromhexaddress(&hex),
+            QtumAddress::new(QtumNetwork::Mainnet).fromhexaddress(&hex)
+        );
+
+        const REGTEST: QtumAddress = QtumAddress::new(QtumNetwork::Regtest);
+        let copied = REGTEST;
+        assert_eq!(copied.network(), REGTEST.network());
+    }
+
+    #[test]
+    fn network_debug_and_equality() {
+        let networks = [
+            (QtumNetwork::Mainnet, "Mainnet"),
+            (QtumNetwork::Testnet, "Testnet"),
+            (QtumNetwork::Regtest, "Regtest"),
+            (QtumNetwork::Custom(0x1f), "Custom(31)"),
+        ];
+
+        for (i, (network, debug)) in networks.iter().enumerate() {
+            assert_eq!(format!("{:?}", network), *debug);
+
+            let copied = *network;
+            assert_eq!(copied, *network);
+            for (j, (other, _)) in networks.iter().enumerate() {
+                assert_eq!(i == j, network == other);
+            }
+        }
+    }
+
+    #[test]
+    fn regtest_shares_testnet_prefix() {
+        assert_eq!(QtumNetwork::Regtest.to_prefix_byte(), 0x78);
+        assert_eq!(
+            QtumNetwork::try_from(QtumNetwork::Regtest.to_prefix_byte()),
+            Ok(QtumNetwork::Testnet)
+        );
+        assert_ne!(QtumNetwork::Regtest, QtumNetwork::Testnet);
+
+        let regtest = QtumAddress::new(QtumNetwork::Regtest);
+        let addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+        let hex = regtest.gethexaddress(addr).unwrap();
+        assert_eq!(hex, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+        assert_eq!(regtest.fromhexaddress(&hex).unwrap(), addr);
+    }
+
+    #[test]
+    fn regtest_address_both_ways() {
+        let regtest = QtumAddress::new(QtumNetwork::Regtest);
+        let hex = "0x0123456789abcdef0123456789abcdef01234567";
+
+        let addr = regtest.fromhexaddress(hex).unwrap();
+        assert_eq!(addr, to_base58(hex, QtumNetwork::Testnet).unwrap());
+        assert_eq!(regtest.gethexaddress(&addr).unwrap(), &hex[2..]);
+
+        assert_eq!(detect_network(&addr), Ok(QtumNetwork::Testnet));
+        assert_eq!(
+            detect_networks(&addr),
+            Ok(&[QtumNetwork::Testnet, QtumNetwork::Regtest][..])
+        );
+        assert_eq!(
+            QtumAddress::new_p2sh(QtumNetwork::Regtest)
+                .gethexaddress(
+                    &QtumAddress::new_p2sh(QtumNetwork::Testnet)
+                        .fromhexaddress(hex)
+                        .unwrap()
+                )
+                .unwrap(),
+            &hex[2..]
+        );
+
+        let mainnet = QtumAddress::mainnet().fromhexaddress(hex).unwrap();
+        assert_eq!(detect_networks(&mainnet), Ok(&[QtumNetwork::Mainnet][..]));
+        assert_eq!(
+            detect_networks("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn bech32_hrp_per_network() {
+        assert_eq!(QtumNetwork::Mainnet.bech32_hrp(), Some("qc"));
+        assert_eq!(QtumNetwork::Testnet.bech32_hrp(), Some("tq"));
+        assert_eq!(QtumNetwork::Regtest.bech32_hrp(), Some("qcrt"));
+        assert_eq!(QtumNetwork::Custom(0x1f).bech32_hrp(), None);
+    }
+
+    #[test]
+    fn network_is_hashable() {
+        let mut networks = std::collections::HashSet::new();
+        networks.insert(QtumNetwork::Mainnet);
+        networks.insert(QtumNetwork::Testnet);
+        networks.insert(QtumNetwork::Mainnet);
+
+        assert_eq!(networks.len(), 2);
+        assert_eq!(format!("{:?}", QtumNetwork::Mainnet), "Mainnet");
+    }
+
+    fn base58check(payload: &[u8]) -> String {
+        let mut bytes = payload.to_vec();
+        bytes.extend_from_slice(&checksum(payload));
+        base58::encode(&bytes)
+    }
+
+    #[test]
+    fn gethexaddress_requires_exact_length() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let mut short = vec![0x78];
+        short.extend_from_slice(&[0x11; 19]);
+        assert_eq!(
+            qtum.gethexaddress(&base58check(&short)),
+            Err(QtumAddressError::InvalidLength {
+                expected: 25,
+                found: 24
+            })
+        );
+
+        // leading zero bytes keep the encoding under the length cap
+        let mut long = vec![0x00, 0x00];
+        long.extend_from_slice(&[0x11; 20]);
+        assert_eq!(
+            qtum.gethexaddress(&base58check(&long)),
+            Err(QtumAddressError::InvalidLength {
+                expected: 25,
+                found: 26
+            })
+        );
+
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        assert_eq!(
+            qtum.gethexaddress(xpub),
+            Err(QtumAddressError::TooLong {
+                max: MAX_BASE58_ADDRESS_LEN,
+                found: 111
+            })
+        );
+    }
+
+    #[test]
+    fn gethexaddress_reports_invalid_character() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(
+            qtum.gethexaddress("0TTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 0,
+                character: '0'
+            })
+        );
+        assert_eq!(
+            qtum.gethexaddress("qTTH1Yr2eKCuDLqOxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 15,
+                character: 'O'
+            })
+        );
+        assert_eq!(
+            qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBl"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 33,
+                character: 'l'
+            })
+        );
+        assert_eq!(
+            qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBé"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 33,
+                character: 'é'
+            })
+        );
+
+        let err = qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUIBLCAjmomQ8pyrBt");
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("0, O, I and l are never part of a valid address"));
+    }
+
+    #[test]
+    fn oversized_input_is_rejected_early() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let input = "q".repeat(1024 * 1024);
+
+        let start = std::time::Instant::now();
+        assert_eq!(
+            qtum.gethexaddress(&input),
+            Err(QtumAddressError::TooLong {
+                max: MAX_BASE58_ADDRESS_LEN,
+                found: input.len()
+            })
+        );
+        assert_eq!(
+            qtum.fromhexaddress(&input),
+            Err(QtumAddressError::TooLong {
+                max: MAX_HEX_ADDRESS_LEN,
+                found: input.len()
+            })
+        );
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn regression_inputs_do_not_panic() {
+        let inputs = [
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyré",
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27daé",
+            "1",
+            "1111111111111111111111111",
+            "qTTH1Yr2eKCuDLqf\0xUyBLCAjmomQ8pyrBt",
+            "0x",
+            "\u{200b}",
+        ];
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        for input in inputs.iter() {
+            assert!(qtum.gethexaddress(input).is_err());
+            assert!(qtum.gethexaddress_unchecked(input).is_err());
+            assert!(qtum.fromhexaddress(input).is_err());
+        }
+    }
+
+    /// Seeded xorshift generator, so randomized tests are reproducible
+    pub(crate) fn xorshift(mut state: u64) -> impl FnMut() -> u64 {
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        }
+    }
+
+    #[test]
+    fn random_input_does_not_panic() {
+        let mut next = xorshift(0x2545_f491_4f6c_dd1d);
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let hex_alphabet = b"0123456789abcdefABCDEFx";
+
+        for _ in 0..5000 {
+            let len = (next() % 48) as usize;
+            let bytes: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            let input = String::from_utf8_lossy(&bytes);
+            let _ = qtum.gethexaddress(&input);
+            let _ = qtum.fromhexaddress(&input);
+
+            let base58: String = (0..len)
+                .map(|_| BITCOIN[(next() % 58) as usize] as char)
+                .collect();
+            let _ = qtum.gethexaddress(&base58);
+            let _ = qtum.gethexaddress_unchecked(&base58);
+
+            let hex: String = (0..len)
+                .map(|_| hex_alphabet[(next() % 23) as usize] as char)
+                .collect();
+            let _ = qtum.fromhexaddress(&hex);
+        }
+    }
+
+    #[test]
+    fn random_hash_round_trip() {
+        let mut next = xorshift(0x9e37_79b9_7f4a_7c15);
+
+        for network in [QtumNetwork::Mainnet, QtumNetwork::Testnet].iter() {
+            let qtum = QtumAddress::new(*network);
+
+            for _ in 0..2000 {
+                let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+                hash.iter_mut().for_each(|byte| *byte = next() as u8);
+                let address = qtum.fromhexaddress_raw(&hash).unwrap();
+
+                assert_eq!(qtum.gethexaddress_raw(&address), Ok(hash), "{}", address);
+            }
+        }
+    }
+
+    #[test]
+    fn random_strings_give_typed_errors() {
+        let mut next = xorshift(0xd1b5_4a32_d192_ed03);
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let alphabet: Vec<char> = core::str::from_utf8(BITCOIN)
+            .unwrap()
+            .chars()
+            .chain("0OIl \t\u{200b}\u{430}é🦀".chars())
+            .collect();
+
+        for _ in 0..5000 {
+            let len = (next() % 40) as usize;
+            let input: String = (0..len)
+                .map(|_| alphabet[(next() % alphabet.len() as u64) as usize])
+                .collect();
+
+            match qtum.gethexaddress(&input) {
+                Ok(hex) => assert_eq!(qtum.fromhexaddress(&hex).unwrap().as_str(), input),
+                Err(err) => assert!(!err.to_string().is_empty(), "{:?}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn gethexaddress_errors() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(qtum.gethexaddress(""), Err(QtumAddressError::EmptyInput));
+        assert_eq!(
+            qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrB0"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 33,
+                character: '0'
+            })
+        );
+        assert_eq!(
+            qtum.gethexaddress("qTTH1Yr2eK"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 25,
+                found: 8
+            })
+        );
+    }
+
+    #[test]
+    fn gethexaddress_rejects_bad_checksum() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let mut bytes = base58::decode("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        let corrupted = base58::encode(&bytes);
+
+        assert_eq!(
+            qtum.gethexaddress(&corrupted),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn gethexaddress_rejects_single_character_mutations() {
+        let qtum_addresses = [
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+            "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG",
+            "qUbxboqjBRp96j3La8D1RYkyqx5uQbJPoW",
+            "qLn9vqbr2Gx3TsVR9QyTVB5mrMoh4x43Uf",
+        ];
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        for addr in qtum_addresses.iter() {
+            for position in 0..addr.len() {
+                for &c in BITCOIN.iter() {
+                    if addr.as_bytes()[position] == c {
+                        continue;
+                    }
+
+                    let mut mutated = addr.as_bytes().to_vec();
+                    mutated[position] = c;
+                    let mutated = String::from_utf8(mutated).unwrap();
+
+                    assert!(qtum.gethexaddress(&mutated).is_err(), "{}", mutated);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gethexaddress_unchecked_skips_checksum() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let mut bytes = base58::decode("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        let corrupted = base58::encode(&bytes);
+
+        assert_eq!(
+            qtum.gethexaddress_unchecked(&corrupted).unwrap(),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+    }
+
+    #[test]
+    fn gethexaddress_rejects_other_network() {
+        let mainnet = QtumAddress::new(QtumNetwork::Mainnet);
+        let testnet = QtumAddress::new(QtumNetwork::Testnet);
+
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        let mainnet_addr = mainnet.fromhexaddress(hex).unwrap();
+        let testnet_addr = testnet.fromhexaddress(hex).unwrap();
+
+        assert_eq!(mainnet.gethexaddress(&mainnet_addr).unwrap(), hex);
+        assert_eq!(
+            testnet.gethexaddress(&mainnet_addr),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x78,
+                found: 0x3a
+            })
+        );
+        assert_eq!(
+            mainnet.gethexaddress(&testnet_addr),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+    }
+
+    #[test]
+    fn gethexaddress_rejects_script_hash_prefix() {
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        let mainnet_p2sh = QtumAddress::new(QtumNetwork::Custom(0x32))
+            .fromhexaddress(hex)
+            .unwrap();
+        let testnet_p2sh = QtumAddress::new(QtumNetwork::Custom(0x6e))
+            .fromhexaddress(hex)
+            .unwrap();
+
+        let wrong_kind = QtumAddressError::WrongAddressType {
+            expected: AddressType::P2PKH,
+            found: AddressType::P2SH,
+        };
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Mainnet).gethexaddress(&mainnet_p2sh),
+            Err(wrong_kind.clone())
+        );
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Testnet).gethexaddress(&testnet_p2sh),
+            Err(wrong_kind.clone())
+        );
+        assert_eq!(
+            wrong_kind.to_string(),
+            "Invalid address: not a pubkeyhash address but a scripthash address"
+        );
+
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Testnet).gethexaddress(&mainnet_p2sh),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x78,
+                found: 0x32
+            })
+        );
+    }
+
+    #[test]
+    fn script_hash_vectors_round_trip() {
+        // (network, script hash, address) encoded with the script hash version byte
+        let vectors = [
+            (
+                QtumNetwork::Mainnet,
+                "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+                "MHo45wyrwgmZy15cF7esPR7fiVjNNBfkMJ",
+            ),
+            (
+                QtumNetwork::Testnet,
+                "49a80104c0d27a9ba29678d07e87a57151107613",
+                "mNtou52DTjTq2DaFAYWvGPkBtMJCXNUNJN",
+            ),
+        ];
+
+        for (network, hex, address) in vectors.iter() {
+            let p2sh = QtumAddress::new_p2sh(*network);
+            let mut hash = [0u8; 20];
+            hash.copy_from_slice(&hex::decode(hex).unwrap());
+
+            assert_eq!(p2sh.fromhexaddress(hex).unwrap(), *address);
+            assert_eq!(QtumAddress::new(*network).from_script_hash(&hash), *address);
+            assert_eq!(p2sh.gethexaddress(address).unwrap(), *hex);
+            assert_eq!(detect_address_type(address), Ok(AddressType::P2SH));
+            assert_eq!(
+                QtumAddress::new(*network).gethexaddress(address),
+                Err(QtumAddressError::WrongAddressType {
+                    expected: AddressType::P2PKH,
+                    found: AddressType::P2SH
+                })
+            );
+
+            // The P2PKH address of the same hash is a different string
+            let key_addr = QtumAddress::new(*network).fromhexaddress(hex).unwrap();
+            assert_ne!(key_addr, *address);
+            assert_eq!(detect_address_type(&key_addr), Ok(AddressType::P2PKH));
+        }
+
+        assert!(vectors[0].2.starts_with('M'));
+    }
+
+    #[test]
+    fn p2sh_conversion() {
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+        for network in [QtumNetwork::Mainnet, QtumNetwork::Testnet].iter() {
+            let p2pkh = QtumAddress::new(*network);
+            let p2sh = QtumAddress::new_p2sh(*network);
+
+            let script_addr = p2sh.fromhexaddress(hex).unwrap();
+            let payload = decode_address_bytes(&script_addr, true).unwrap();
+            assert_eq!(payload[0], network.to_script_prefix_byte());
+            assert_eq!(p2sh.gethexaddress(&script_addr).unwrap(), hex);
+
+            let key_addr = p2pkh.fromhexaddress(hex).unwrap();
+            assert_eq!(
+                p2sh.gethexaddress(&key_addr),
+                Err(QtumAddressError::WrongAddressType {
+                    expected: AddressType::P2SH,
+                    found: AddressType::P2PKH
+                })
+            );
+
+            for converter in [&p2pkh, &p2sh].iter() {
+                assert_eq!(
+                    converter.gethexaddress_typed(&key_addr).unwrap(),
+                    (
+                        HexAddress::from(decode_hex(hex).unwrap()),
+                        AddressType::P2PKH
+                    )
+                );
+                assert_eq!(
+                    converter.gethexaddress_typed(&script_addr).unwrap().1,
+                    AddressType::P2SH
+                );
+            }
+        }
+
+        assert_eq!(QtumNetwork::Mainnet.to_script_prefix_byte(), 0x32);
+        assert_eq!(QtumNetwork::Testnet.to_script_prefix_byte(), 0x6e);
+        assert_eq!(QtumNetwork::Regtest.to_script_prefix_byte(), 0x6e);
+
+        let mainnet_script = QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+            .fromhexaddress(hex)
+            .unwrap();
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Testnet).gethexaddress_typed(&mainnet_script),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x78,
+                found: 0x32
+            })
+        );
+    }
+
+    #[test]
+    fn network_mismatch_names_both_networks() {
+        let err = QtumAddressError::NetworkMismatch {
+            expected: 0x3a,
+            found: 0x78,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid address: address belongs to testnet (0x78) but converter is configured for mainnet (0x3a)"
+        );
+
+        let err = QtumAddress::new(QtumNetwork::Custom(0x55))
+            .gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid address: address belongs to testnet (0x78) but converter is configured for custom(0x55)"
+        );
+    }
+
+    #[test]
+    fn fromhexaddress_accepts_prefix_and_any_case() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let expected = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+
+        let hex = qtum.gethexaddress(expected).unwrap();
+        assert_eq!(
+            qtum.fromhexaddress(&QtumAddress::with_hex_prefix(&hex).unwrap())
+                .unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            qtum.fromhexaddress("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            qtum.fromhexaddress("0X6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            qtum.fromhexaddress("006c89a1a6ca2ae7c00b248bb2832d6f480f27da68"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 42
+            })
+        );
+    }
+
+    #[test]
+    fn fromhexaddress_prefixed_matches_bare() {
+        let eth_addresses = [
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+            "49a80104c0d27a9ba29678d07e87a57151107613",
+            "7926223070547d2d15b2ef5e7383e541c338ffe9",
+            "2352be3db3177f0a07efbe6da5857615b8c9901d",
+        ];
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        for addr in eth_addresses.iter() {
+            let bare = qtum.fromhexaddress(addr).unwrap();
+            let lower = qtum.fromhexaddress(&format!("0x{}", addr)).unwrap();
+            let upper = qtum.fromhexaddress(&format!("0X{}", addr)).unwrap();
+
+            assert_eq!(bare, lower);
+            assert_eq!(bare, upper);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn gethexaddress_bytes_is_gethexaddress_raw() {
+        let qtum = QtumAddress::testnet();
+
+        for address in &[
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu",
+        ] {
+            assert_eq!(
+                qtum.gethexaddress_bytes(address),
+                qtum.gethexaddress_raw(address)
+            );
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn addprefix_is_idempotent() {
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        let prefixed = QtumAddress::addprefix(hex);
+
+        assert_eq!(prefixed, format!("0x{}", hex));
+        assert_eq!(QtumAddress::addprefix(&prefixed), prefixed);
+        assert_eq!(
+            QtumAddress::addprefix(&format!("0X{}", hex)),
+            format!("0x{}", hex)
+        );
+    }
+
+    #[test]
+    fn removeprefix_strips_once() {
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+        assert_eq!(QtumAddress::removeprefix(hex), hex);
+        assert_eq!(QtumAddress::removeprefix(&format!("0x{}", hex)), hex);
+        assert_eq!(QtumAddress::removeprefix(&format!("0X{}", hex)), hex);
+        assert_eq!(QtumAddress::removeprefix("0x0xab"), "0xab");
+    }
+
+    #[test]
+    fn hex_prefix_helpers_validate() {
+        let hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+        let prefixed = format!("0x{}", hex);
+
+        assert_eq!(QtumAddress::with_hex_prefix(hex), Ok(prefixed.clone()));
+        assert_eq!(
+            QtumAddress::with_hex_prefix(&prefixed),
+            Ok(prefixed.clone())
+        );
+        assert_eq!(
+            QtumAddress::with_hex_prefix(&format!("0X{}", hex)),
+            Ok(prefixed.clone())
+        );
+        assert_eq!(QtumAddress::strip_hex_prefix(&prefixed), Ok(hex));
+        assert_eq!(QtumAddress::strip_hex_prefix(hex), Ok(hex));
+
+        let double = format!("0x{}", prefixed);
+        assert_eq!(
+            QtumAddress::with_hex_prefix(&double),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 42
+            })
+        );
+        assert_eq!(
+            QtumAddress::strip_hex_prefix(&double),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 42
+            })
+        );
+
+        assert_eq!(
+            QtumAddress::with_hex_prefix("hello"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 5
+            })
+        );
+        assert_eq!(
+            QtumAddress::strip_hex_prefix("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27dag8"),
+            Err(QtumAddressError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn suggest_correction_recovers_single_typo() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+
+        assert_eq!(qtum.suggest_correction(addr), None);
+        assert_eq!(
+            qtum.suggest_correction("qTTH1Yr2eKCuDLqfxUyBLCAjmcmQ8pyrBt")
+                .unwrap(),
+            addr
+        );
+        assert_eq!(
+            qtum.suggest_correction("qTTH1Yr2eKCuDLq0xUyBLCAjmomQ8pyrBt")
+                .unwrap(),
+            addr
+        );
+    }
+
+    #[test]
+    fn suggest_correction_refuses_to_guess() {
+        let testnet = QtumAddress::new(QtumNetwork::Testnet);
+        let mainnet = QtumAddress::new(QtumNetwork::Mainnet);
+
+        assert_eq!(
+            testnet.suggest_correction("qTTH1Yr2eKCuDLqfxUyBLCAjmcmQ8pyrBs"),
+            None
+        );
+        assert_eq!(
+            mainnet.suggest_correction("qTTH1Yr2eKCuDLqfxUyBLCAjmcmQ8pyrBt"),
+            None
+        );
+    }
+
+    #[test]
+    fn byte_conversions_match_string_conversions() {
+        let qtum_addresses = [
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+            "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG",
+            "qUbxboqjBRp96j3La8D1RYkyqx5uQbJPoW",
+            "qLn9vqbr2Gx3TsVR9QyTVB5mrMoh4x43Uf",
+            "qTCCy8qy7pW94EApdoBjYc1vQ2w68UnXPi",
+            "qWMi6ne9mDQFatRGejxdDYVUV9rQVkAFGp",
+            "qLcshhsRS6HKeTKRYFdpXnGVZxw96QQcfm",
+            "qW28njWueNpBXYWj2KDmtFG2gbLeALeHfV",
+        ];
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        for addr in qtum_addresses.iter() {
+            let bytes = qtum.gethexaddress_raw(addr).unwrap();
+            let hex = qtum.gethexaddress(addr).unwrap();
+            assert_eq!(hex::decode(hex.as_str()).unwrap(), bytes);
+
+            assert_eq!(qtum.fromhexaddress_bytes(&bytes).unwrap(), *addr);
+        }
+
+        assert_eq!(
+            qtum.gethexaddress_raw("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn from_hash160_matches_hex_fixtures() {
+        let fixtures = [
+            (
+                "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+                "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt",
+            ),
+            (
+                "49a80104c0d27a9ba29678d07e87a57151107613",
+                "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG",
+            ),
+            (
+                "7926223070547d2d15b2ef5e7383e541c338ffe9",
+                "qUbxboqjBRp96j3La8D1RYkyqx5uQbJPoW",
+            ),
+        ];
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let p2sh = QtumAddress::new_p2sh(QtumNetwork::Testnet);
+
+        for (hex, addr) in fixtures.iter() {
+            let mut hash = [0u8; 20];
+            hash.copy_from_slice(&hex::decode(hex).unwrap());
+
+            assert_eq!(qtum.from_hash160(&hash), *addr);
+            assert_eq!(qtum.fromhexaddress_bytes(&hash[..]).unwrap(), *addr);
+            assert_eq!(p2sh.from_hash160(&hash), *addr);
+            assert_ne!(p2sh.fromhexaddress_raw(&hash).unwrap(), *addr);
+            assert_eq!(qtum.fromhexaddress_raw(&hash).unwrap(), *addr);
+            assert_eq!(qtum.fromhexaddress_raw(&hash), qtum.fromhexaddress(hex));
+            assert_eq!(qtum.gethexaddress_raw(addr).unwrap(), hash);
+        }
+
+        assert_eq!(
+            qtum.fromhexaddress_bytes(&[0x6c; 19]),
+            Err(QtumAddressError::InvalidLength {
+                expected: 20,
+                found: 19
+            })
+        );
+        assert_eq!(
+            qtum.fromhexaddress_bytes(&[0x6c; 21]),
+            Err(QtumAddressError::InvalidLength {
+                expected: 20,
+                found: 21
+            })
+        );
+    }
+
+    #[test]
+    fn detect_network_from_prefix() {
+        let hash = [0x6c; 20];
+
+        assert_eq!(
+            detect_network("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Ok(QtumNetwork::Testnet)
+        );
+        assert_eq!(
+            detect_network(&encode_base58check(0x3a, &hash)),
+            Ok(QtumNetwork::Mainnet)
+        );
+        assert_eq!(
+            detect_network(&encode_base58check(0x32, &hash)),
+            Ok(QtumNetwork::Mainnet)
+        );
+        assert_eq!(
+            detect_network(&encode_base58check(0x00, &hash)),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x00 })
+        );
+        assert_eq!(
+            detect_network("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn network_from_address() {
+        let hash = [0x6c; 20];
+        let mainnet = encode_base58check(0x3a, &hash);
+        let mainnet_p2sh = encode_base58check(0x32, &hash);
+        let testnet_p2sh = encode_base58check(0x6e, &hash);
+
+        for (address, mainnet) in &[
+            (mainnet.as_str(), true),
+            (mainnet_p2sh.as_str(), true),
+            ("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", false),
+            (testnet_p2sh.as_str(), false),
+        ] {
+            let network = QtumNetwork::from_address(address).unwrap();
+            assert_eq!(network.is_mainnet(), *mainnet);
+            assert_eq!(network.is_testnet(), !*mainnet);
+
+            let typed = Address::from_base58(address).unwrap();
+            assert_eq!(typed.network(), network);
+            assert_eq!(typed.is_mainnet(), *mainnet);
+            assert_eq!(typed.is_testnet(), !*mainnet);
+        }
+
+        // Bitcoin genesis block address, version byte 0x00
+        let err = QtumNetwork::from_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").unwrap_err();
+        assert_eq!(err, QtumAddressError::UnknownNetwork { prefix: 0x00 });
+        assert!(err.to_string().contains("unknown network"));
+
+        assert!(!QtumNetwork::Regtest.is_testnet());
+        assert!(!QtumNetwork::Custom(0x3a).is_mainnet());
+    }
+
+    #[test]
+    fn gethexaddress_any_reports_network() {
+        let hexes = [
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+            "49a80104c0d27a9ba29678d07e87a57151107613",
+            "7926223070547d2d15b2ef5e7383e541c338ffe9",
+        ];
+
+        for network in &[QtumNetwork::Mainnet, QtumNetwork::Testnet] {
+            for qtum in &[QtumAddress::new(*network), QtumAddress::new_p2sh(*network)] {
+                for hex in &hexes {
+                    let addr = qtum.fromhexaddress(hex).unwrap();
+                    let (detected, decoded) = QtumAddress::gethexaddress_any(&addr).unwrap();
+                    assert_eq!(detected, *network);
+                    assert_eq!(decoded, *hex);
+                }
+            }
+        }
+
+        assert_eq!(
+            QtumAddress::gethexaddress_any(&encode_base58check(0x00, &[0x6c; 20])),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x00 })
+        );
+        assert_eq!(
+            QtumAddress::gethexaddress_any("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn base58check_payload_lengths() {
+        let mut next = xorshift(0x853c_49e6_748f_ea9b);
+
+        for len in &[0, 1, 20, 21, 32, 33, 64, 80] {
+            let payload: Vec<u8> = (0..*len).map(|_| next() as u8).collect();
+            for version in &[0x00, 0x3a, 0x80, 0xff] {
+                let encoded = encode_base58check(*version, &payload);
+                assert_eq!(
+                    decode_base58check(&encoded),
+                    Ok((*version, payload.clone()))
+                );
+            }
+        }
+
+        // Uncompressed and compressed WIFs carry 32 and 33-byte payloads
+        let mut key = vec![0u8; 32];
+        key[31] = 1;
+        key.push(0x01);
+        let wif = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+        assert_eq!(encode_base58check(0x80, &key), wif);
+        assert_eq!(decode_base58check(wif), Ok((0x80, key)));
+    }
+
+    #[test]
+    fn base58check_multi_byte_prefix() {
+        // Master key of BIP32 test vector 1
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        let payload = hex::decode(concat!(
+            "00",       // depth
+            "00000000", // parent fingerprint
+            "00000000", // child number
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+            "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            encode_base58check_prefixed(&[0x04, 0x88, 0xb2, 0x1e], &payload),
+            xpub
+        );
+        assert_eq!(
+            decode_base58check_prefixed(xpub, 4),
+            Ok((vec![0x04, 0x88, 0xb2, 0x1e], payload))
+        );
+    }
+
+    #[test]
+    fn base58check_decode_errors() {
+        assert_eq!(decode_base58check(""), Err(QtumAddressError::EmptyInput));
+        assert_eq!(
+            decode_base58check("1111"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(
+            decode_base58check("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            decode_base58check("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrB0"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 33,
+                character: '0'
+            })
+        );
+        assert_eq!(
+            decode_base58check_prefixed("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", 26),
+            Err(QtumAddressError::InvalidLength {
+                expected: 30,
+                found: 25
+            })
+        );
+
+        let longest = "1".repeat(MAX_BASE58CHECK_LEN);
+        assert!(matches!(
+            decode_base58check(&longest),
+            Err(QtumAddressError::InvalidChecksum)
+        ));
+        let oversized = "1".repeat(MAX_BASE58CHECK_LEN + 1);
+        for result in &[
+            decode_base58check(&oversized).map(|(_, payload)| payload),
+            decode_base58check_prefixed(&oversized, 4).map(|(_, payload)| payload),
+        ] {
+            assert_eq!(
+                *result,
+                Err(QtumAddressError::TooLong {
+                    max: MAX_BASE58CHECK_LEN,
+                    found: MAX_BASE58CHECK_LEN + 1
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn checksum_is_double_sha256() {
+        // sha256d("hello") = 9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50
+        assert_eq!(checksum(b"hello"), [0x95, 0x95, 0xc9, 0xdf]);
+    }
+
+    #[test]
+    fn fromhexaddress_errors() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(qtum.fromhexaddress(""), Err(QtumAddressError::EmptyInput));
+        assert_eq!(
+            qtum.fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 38
+            })
+        );
+        assert_eq!(
+            qtum.fromhexaddress("zz89a1a6ca2ae7c00b248bb2832d6f480f27da68"),
+            Err(QtumAddressError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn error_display_keeps_message() {
+        let err = QtumAddressError::InvalidHex;
+        assert!(err.to_string().starts_with("Invalid address"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error_is_owned_and_thread_safe() {
+        fn assert_error<E: std::error::Error + Send + Sync + 'static>(_: &E) {}
+
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let err = qtum.fromhexaddress("").unwrap_err();
+        assert_error(&err);
+    }
 }
@@ -0,0 +1,354 @@
+//! Compile-time parsing behind the `qtum_addr!` macro
+//!
+//! Everything here is `const fn` and panics on invalid input, which fails the build when
+//! evaluated in a constant.
+use crate::address::classify_version;
+use crate::{
+    Address, AddressType, QtumNetwork, ADDRESS_PAYLOAD_LEN, BITCOIN, HEX_ADDRESS_LEN,
+    RAW_ADDRESS_LEN, VERSIONED_PAYLOAD_LEN,
+};
+
+/// Creates an `Address` constant from a literal, validated at compile time.
+///
+/// A single base58 literal must carry a valid checksum and the version byte of a known
+/// network. A 40 character hex literal, with an optional `0x` prefix, takes the network as
+/// second argument and becomes a pubkey hash address.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// const TREASURY: Address = qtum_addr!("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+/// const CONTRACT: Address =
+///     qtum_addr!("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68", QtumNetwork::Testnet);
+///
+/// assert_eq!(TREASURY, CONTRACT);
+/// ```
+///
+/// Invalid literals fail the build, the panic messages are checked by the unit tests:
+///
+/// ```compile_fail
+/// use qtum_address_rust::*;
+///
+/// const TYPO: Address = qtum_addr!("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu");
+/// ```
+///
+/// ```compile_fail
+/// use qtum_address_rust::*;
+///
+/// const SHORT: Address =
+///     qtum_addr!("0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da6", QtumNetwork::Testnet);
+/// ```
+#[macro_export]
+macro_rules! qtum_addr {
+    ($address:literal) => {{
+        const ADDRESS: $crate::Address = $crate::literal::base58($address);
+        ADDRESS
+    }};
+    ($hex:literal, $network:expr) => {{
+        const ADDRESS: $crate::Address = $crate::literal::hex($hex, $network);
+        ADDRESS
+    }};
+}
+
+/// Parsing a base58check literal
+pub const fn base58(address: &str) -> Address {
+    let bytes = decode_base58(address.as_bytes());
+
+    let check = sha256(&sha256(split_payload(&bytes)));
+    if check[0] != bytes[21]
+        || check[1] != bytes[22]
+        || check[2] != bytes[23]
+        || check[3] != bytes[24]
+    {
+        panic!("Qtum address literal has an invalid checksum");
+    }
+
+    let (network, kind) = match classify_version(bytes[0]) {
+        Some(classified) => classified,
+        None => panic!("Qtum address literal has an unknown version byte"),
+    };
+
+    let mut hash160 = [0u8; ADDRESS_PAYLOAD_LEN];
+    let mut i = 0;
+    while i < ADDRESS_PAYLOAD_LEN {
+        hash160[i] = bytes[i + 1];
+        i += 1;
+    }
+
+    Address::new(network, kind, hash160)
+}
+
+/// Parsing a hex literal as a pubkey hash of the given network
+pub const fn hex(address: &str, network: QtumNetwork) -> Address {
+    let mut bytes = address.as_bytes();
+    if bytes.len() >= 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+        let (_, rest) = bytes.split_at(2);
+        bytes = rest;
+    }
+
+    if bytes.len() != HEX_ADDRESS_LEN {
+        panic!("Qtum hex address literal must be 40 hex characters");
+    }
+
+    let mut hash160 = [0u8; ADDRESS_PAYLOAD_LEN];
+    let mut i = 0;
+    while i < ADDRESS_PAYLOAD_LEN {
+        hash160[i] = (hex_digit(bytes[2 * i]) << 4) | hex_digit(bytes[2 * i + 1]);
+        i += 1;
+    }
+
+    Address::new(network, AddressType::P2PKH, hash160)
+}
+
+const fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("Qtum hex address literal contains a non-hex character"),
+    }
+}
+
+/// Decoding base58 into exactly 25 bytes
+const fn decode_base58(input: &[u8]) -> [u8; RAW_ADDRESS_LEN] {
+    let mut bytes = [0u8; RAW_ADDRESS_LEN];
+
+    let mut i = 0;
+    while i < input.len() {
+        let mut carry = match base58_digit(input[i]) {
+            Some(digit) => digit as u32,
+            None => panic!("Qtum address literal contains a non-base58 character"),
+        };
+
+        let mut j = RAW_ADDRESS_LEN;
+        while j > 0 {
+            j -= 1;
+            carry += bytes[j] as u32 * 58;
+            bytes[j] = carry as u8;
+            carry >>= 8;
+        }
+
+        if carry != 0 {
+            panic!("Qtum address literal is too long");
+        }
+        i += 1;
+    }
+
+    // Every leading '1' stands for one leading zero byte, the rest must fill the remaining bytes
+    let mut zeros = 0;
+    while zeros < input.len() && input[zeros] == BITCOIN[0] {
+        zeros += 1;
+    }
+    let mut k = 0;
+    while k < zeros {
+        if k >= RAW_ADDRESS_LEN || bytes[k] != 0 {
+            panic!("Qtum address literal is too long");
+        }
+        k += 1;
+    }
+    if zeros < RAW_ADDRESS_LEN && bytes[zeros] == 0 {
+        panic!("Qtum address literal is too short");
+    }
+
+    bytes
+}
+
+const fn base58_digit(c: u8) -> Option<u8> {
+    let mut i = 0;
+    while i < BITCOIN.len() {
+        if BITCOIN[i] == c {
+            return Some(i as u8);
+        }
+        i += 1;
+    }
+    None
+}
+
+const fn split_payload(bytes: &[u8; RAW_ADDRESS_LEN]) -> &[u8] {
+    let (payload, _) = bytes.split_at(VERSIONED_PAYLOAD_LEN);
+    payload
+}
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Single block SHA-256, enough for the 21 and 32 byte inputs of a base58check checksum
+const fn sha256(data: &[u8]) -> [u8; 32] {
+    if data.len() > 55 {
+        panic!("const sha256 only handles a single block");
+    }
+
+    let mut block = [0u8; 64];
+    let mut i = 0;
+    while i < data.len() {
+        block[i] = data[i];
+        i += 1;
+    }
+    block[data.len()] = 0x80;
+    let bits = (data.len() as u64) * 8;
+    let mut i = 0;
+    while i < 8 {
+        block[63 - i] = (bits >> (8 * i)) as u8;
+        i += 1;
+    }
+
+    let mut w = [0u32; 64];
+    let mut t = 0;
+    while t < 16 {
+        w[t] = u32::from_be_bytes([
+            block[4 * t],
+            block[4 * t + 1],
+            block[4 * t + 2],
+            block[4 * t + 3],
+        ]);
+        t += 1;
+    }
+    while t < 64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+        t += 1;
+    }
+
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    let mut v = h;
+    let mut t = 0;
+    while t < 64 {
+        let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+        let ch = (v[4] & v[5]) ^ (!v[4] & v[6]);
+        let temp1 = v[7]
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[t])
+            .wrapping_add(w[t]);
+        let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+        let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+        let temp2 = s0.wrapping_add(maj);
+
+        v[7] = v[6];
+        v[6] = v[5];
+        v[5] = v[4];
+        v[4] = v[3].wrapping_add(temp1);
+        v[3] = v[2];
+        v[2] = v[1];
+        v[1] = v[0];
+        v[0] = temp1.wrapping_add(temp2);
+        t += 1;
+    }
+
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 8 {
+        h[i] = h[i].wrapping_add(v[i]);
+        let word = h[i].to_be_bytes();
+        out[4 * i] = word[0];
+        out[4 * i + 1] = word[1];
+        out[4 * i + 2] = word[2];
+        out[4 * i + 3] = word[3];
+        i += 1;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode_base58check, QtumAddress};
+    use alloc::vec::Vec;
+    use bitcoin_hashes::{sha256, Hash};
+
+    #[test]
+    fn const_sha256_matches_bitcoin_hashes() {
+        let mut data = Vec::new();
+        for len in 0..=55u8 {
+            assert_eq!(
+                super::sha256(&data),
+                sha256::Hash::hash(&data).into_inner(),
+                "length {}",
+                len
+            );
+            data.push(len.wrapping_mul(151));
+        }
+    }
+
+    #[test]
+    fn literals_match_runtime_parsing() {
+        const BASE58: Address = qtum_addr!("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+        const HEX: Address = qtum_addr!(
+            "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68",
+            QtumNetwork::Testnet
+        );
+
+        let runtime: Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+        assert_eq!(BASE58, runtime);
+        assert_eq!(HEX, runtime);
+
+        let script = QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+            .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap();
+        assert_eq!(base58(&script), script.parse().unwrap());
+    }
+
+    #[test]
+    fn leading_zero_bytes() {
+        let address = encode_base58check(0x3a, &[0x00; 20]);
+        assert_eq!(base58(&address).hash160(), [0x00; 20]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid checksum")]
+    fn corrupted_checksum_panics() {
+        base58("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu");
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown version byte")]
+    fn unknown_version_panics() {
+        base58(&encode_base58check(0x00, &[0x11; 20]));
+    }
+
+    #[test]
+    #[should_panic(expected = "too short")]
+    fn short_literal_panics() {
+        base58("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyr");
+    }
+
+    #[test]
+    #[should_panic(expected = "too long")]
+    fn long_literal_panics() {
+        base58("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBtt");
+    }
+
+    #[test]
+    #[should_panic(expected = "non-hex")]
+    fn bad_hex_panics() {
+        hex(
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27dag8",
+            QtumNetwork::Testnet,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "40 hex characters")]
+    fn short_hex_panics() {
+        hex(
+            "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da6",
+            QtumNetwork::Testnet,
+        );
+    }
+}
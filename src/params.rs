@@ -0,0 +1,120 @@
+use crate::{QtumNetwork, MAINNET_PREFIX, REGTEST_PREFIX, TESTNET_PREFIX};
+
+/// Chain parameters of a network, as found in Qtum Core's chainparams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct NetworkParams {
+    /// Version byte of pay to pubkey hash addresses
+    pub pubkey_address: u8,
+    /// Version byte of pay to script hash addresses
+    pub script_address: u8,
+    /// Version byte of WIF private keys
+    pub wif: u8,
+    /// Human readable part of native segwit addresses
+    pub bech32_hrp: &'static str,
+    /// Version bytes of extended public keys (xpub)
+    pub ext_public_key: [u8; 4],
+    /// Version bytes of extended private keys (xprv)
+    pub ext_secret_key: [u8; 4],
+}
+
+const MAINNET: NetworkParams = NetworkParams {
+    pubkey_address: MAINNET_PREFIX,
+    script_address: 0x32,
+    wif: 0x80,
+    bech32_hrp: "qc",
+    ext_public_key: [0x04, 0x88, 0xb2, 0x1e],
+    ext_secret_key: [0x04, 0x88, 0xad, 0xe4],
+};
+
+const TESTNET: NetworkParams = NetworkParams {
+    pubkey_address: TESTNET_PREFIX,
+    script_address: 0x6e,
+    wif: 0xef,
+    bech32_hrp: "tq",
+    ext_public_key: [0x04, 0x35, 0x87, 0xcf],
+    ext_secret_key: [0x04, 0x35, 0x83, 0x94],
+};
+
+/// Regtest only differs from testnet in its bech32 prefix
+const REGTEST: NetworkParams = NetworkParams {
+    pubkey_address: REGTEST_PREFIX,
+    bech32_hrp: "qcrt",
+    ..TESTNET
+};
+
+impl QtumNetwork {
+    /// Chain parameters of the network, `None` for `Custom` networks which only know their
+    /// version byte.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let params = QtumNetwork::Mainnet.params().unwrap();
+    /// assert_eq!(params.script_address, 0x32);
+    /// assert_eq!(params.bech32_hrp, "qc");
+    /// ```
+    pub const fn params(&self) -> Option<&'static NetworkParams> {
+        match self.params_or_prefix() {
+            Ok(params) => Some(params),
+            Err(_) => None,
+        }
+    }
+
+    /// Chain parameters of a named network, or the version byte of a `Custom` network which
+    /// stands for both kinds of address
+    pub(crate) const fn params_or_prefix(&self) -> Result<&'static NetworkParams, u8> {
+        match self {
+            QtumNetwork::Mainnet => Ok(&MAINNET),
+            QtumNetwork::Testnet => Ok(&TESTNET),
+            QtumNetwork::Regtest => Ok(&REGTEST),
+            QtumNetwork::Custom(prefix) => Err(*prefix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn params_match_chainparams() {
+        let mainnet = QtumNetwork::Mainnet.params().unwrap();
+        assert_eq!(mainnet.pubkey_address, 0x3a);
+        assert_eq!(mainnet.script_address, 0x32);
+        assert_eq!(mainnet.wif, 0x80);
+        assert_eq!(mainnet.bech32_hrp, "qc");
+        assert_eq!(u32::from_be_bytes(mainnet.ext_public_key), 0x0488_b21e);
+        assert_eq!(u32::from_be_bytes(mainnet.ext_secret_key), 0x0488_ade4);
+
+        let testnet = QtumNetwork::Testnet.params().unwrap();
+        assert_eq!(testnet.pubkey_address, 0x78);
+        assert_eq!(testnet.script_address, 0x6e);
+        assert_eq!(testnet.wif, 0xef);
+        assert_eq!(testnet.bech32_hrp, "tq");
+        assert_eq!(u32::from_be_bytes(testnet.ext_public_key), 0x0435_87cf);
+        assert_eq!(u32::from_be_bytes(testnet.ext_secret_key), 0x0435_8394);
+
+        let regtest = QtumNetwork::Regtest.params().unwrap();
+        assert_eq!(regtest.bech32_hrp, "qcrt");
+        assert_eq!(
+            NetworkParams {
+                bech32_hrp: "tq",
+                ..*regtest
+            },
+            *testnet
+        );
+
+        assert_eq!(QtumNetwork::Custom(0x1f).params(), None);
+    }
+
+    #[test]
+    fn accessors_read_params() {
+        for network in QtumNetwork::all() {
+            let params = network.params().unwrap();
+            assert_eq!(network.to_prefix_byte(), params.pubkey_address);
+            assert_eq!(network.to_script_prefix_byte(), params.script_address);
+            assert_eq!(network.bech32_hrp(), Some(params.bech32_hrp));
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Common types and functions, `use qtum_address_rust::prelude::*;` is enough for most uses
+pub use crate::{
+    to_base58, to_hex, Address, AddressType, Base58Address, HexAddress, QtumAddress,
+    QtumAddressError, QtumNetwork, ToBase58Address, ToHexAddress,
+};
@@ -0,0 +1,159 @@
+use crate::QtumAddressError;
+
+/// Checks the first `limit` characters for whitespace, invisible characters and homoglyphs.
+///
+/// The limit keeps the scan cheap for oversized inputs, which are rejected by the length check
+/// afterwards.
+pub(crate) fn check_characters(address: &str, limit: usize) -> Result<(), QtumAddressError> {
+    for (position, character) in address.chars().take(limit).enumerate() {
+        if character.is_whitespace() || is_invisible(character) {
+            return Err(QtumAddressError::Whitespace {
+                position,
+                character,
+            });
+        }
+
+        if let Some(lookalike) = lookalike(character) {
+            return Err(QtumAddressError::Homoglyph {
+                position,
+                character,
+                lookalike,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Zero-width and formatting characters that are not covered by `char::is_whitespace`
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00ad}' | '\u{180e}' | '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}'
+    )
+}
+
+/// ASCII character resembled by a Cyrillic, Greek or fullwidth character
+fn lookalike(c: char) -> Option<char> {
+    let ascii = match c {
+        'а' => 'a',
+        'е' => 'e',
+        'о' | 'ο' => 'o',
+        'р' | 'ρ' => 'p',
+        'с' => 'c',
+        'у' => 'y',
+        'х' => 'x',
+        'і' => 'i',
+        'ј' => 'j',
+        'ѕ' => 's',
+        'А' | 'Α' => 'A',
+        'В' | 'Β' => 'B',
+        'Е' | 'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Н' | 'Η' => 'H',
+        'І' | 'Ι' => 'I',
+        'К' | 'Κ' => 'K',
+        'М' | 'Μ' => 'M',
+        'Ν' => 'N',
+        'О' | 'Ο' => 'O',
+        'Р' | 'Ρ' => 'P',
+        'С' => 'C',
+        'Т' | 'Τ' => 'T',
+        'Υ' => 'Y',
+        'Х' | 'Χ' => 'X',
+        '\u{ff10}'..='\u{ff19}' => (b'0' + (c as u32 - 0xff10) as u8) as char,
+        '\u{ff21}'..='\u{ff3a}' => (b'A' + (c as u32 - 0xff21) as u8) as char,
+        '\u{ff41}'..='\u{ff5a}' => (b'a' + (c as u32 - 0xff41) as u8) as char,
+        _ => return None,
+    };
+
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn surrounding_whitespace() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(
+            qtum.gethexaddress("  qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt\n"),
+            Err(QtumAddressError::Whitespace {
+                position: 0,
+                character: ' '
+            })
+        );
+        assert_eq!(
+            qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt\n"),
+            Err(QtumAddressError::Whitespace {
+                position: 34,
+                character: '\n'
+            })
+        );
+    }
+
+    #[test]
+    fn zero_width_space() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let err = qtum
+            .gethexaddress("qTTH1Yr2eKCuDLqf\u{200b}xUyBLCAjmomQ8pyrBt")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            QtumAddressError::Whitespace {
+                position: 16,
+                character: '\u{200b}'
+            }
+        );
+        assert!(err.to_string().contains("U+200B"));
+    }
+
+    #[test]
+    fn nbsp_padded_hex() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        // Surrounding whitespace of hex addresses is trimmed, whitespace inside is not
+        assert_eq!(
+            qtum.fromhexaddress("\u{a0}6c89a1a6ca2ae7c00b248bb2832d6f480f27da68\u{a0}")
+                .unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+        assert_eq!(
+            qtum.fromhexaddress("6c89a1a6ca2ae7c00b248bb2\u{a0}832d6f480f27da68"),
+            Err(QtumAddressError::Whitespace {
+                position: 24,
+                character: '\u{a0}'
+            })
+        );
+    }
+
+    #[test]
+    fn homoglyphs() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let err = qtum
+            .gethexaddress("qTTH1Yr2eKCuDLqfхUyBLCAjmomQ8pyrBt")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            QtumAddressError::Homoglyph {
+                position: 16,
+                character: 'х',
+                lookalike: 'x'
+            }
+        );
+        assert!(err.to_string().contains("looks like 'x'"));
+
+        assert_eq!(
+            qtum.fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da6８"),
+            Err(QtumAddressError::Homoglyph {
+                position: 39,
+                character: '８',
+                lookalike: '8'
+            })
+        );
+    }
+}
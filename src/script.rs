@@ -0,0 +1,844 @@
+//! Locking scripts of standard outputs
+
+use crate::{
+    decode_address_bytes, hash160, Address, AddressType, Base58Address, QtumAddress,
+    QtumAddressError, TxAddress, WitnessProgram, ADDRESS_PAYLOAD_LEN, WSH_PROGRAM_LEN,
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+pub(crate) const OP_0: u8 = 0x00;
+pub(crate) const OP_DUP: u8 = 0x76;
+pub(crate) const OP_HASH160: u8 = 0xa9;
+pub(crate) const OP_EQUAL: u8 = 0x87;
+pub(crate) const OP_EQUALVERIFY: u8 = 0x88;
+pub(crate) const OP_CHECKSIG: u8 = 0xac;
+pub(crate) const OP_CHECKMULTISIG: u8 = 0xae;
+
+/// `OP_1` to `OP_16` are `OP_1 - 1 + n`
+pub(crate) const OP_1: u8 = 0x51;
+pub(crate) const OP_16: u8 = 0x60;
+
+/// Largest number of keys in a bare multisig script
+const MAX_MULTISIG_KEYS: usize = 16;
+
+/// Largest redeem script a script hash output can be spent with
+pub const MAX_REDEEM_SCRIPT_LEN: usize = 520;
+
+/// Push of the next 20 bytes
+pub(crate) const OP_PUSHBYTES_20: u8 = 0x14;
+
+/// Largest opcode pushing the next `opcode` bytes
+const OP_PUSHBYTES_75: u8 = 0x4b;
+/// Pushes with a 1, 2 and 4-byte little endian length
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_PUSHDATA2: u8 = 0x4d;
+const OP_PUSHDATA4: u8 = 0x4e;
+
+/// Standard output templates with an address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ScriptKind {
+    /// `OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG`
+    P2PKH,
+    /// `OP_HASH160 <20 bytes> OP_EQUAL`
+    P2SH,
+    /// `<public key> OP_CHECKSIG`, shown as the pubkey hash address of the key
+    P2PK,
+    /// `OP_0 <20 bytes>`
+    P2WPKH,
+    /// `OP_0 <32 bytes>`
+    P2WSH,
+    /// `OP_1` to `OP_16` followed by a 2 to 40 byte program
+    WitnessUnknown,
+}
+
+/// Address paid to by a locking script
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParsedScript {
+    /// A standard template with its base58 or bech32 address
+    Address {
+        /// Detected template
+        kind: ScriptKind,
+        /// Address of the configured network
+        address: String,
+    },
+    /// `OP_RETURN`, contract and any other script without an address
+    NonStandard,
+}
+
+/// Script hash address and redeem script of a multisig, as returned by `createmultisig`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MultisigInfo {
+    /// Script hash address of the redeem script
+    pub address: Base58Address,
+    /// Hex encoded `OP_m <pubkey>... OP_n OP_CHECKMULTISIG` script
+    pub redeem_script: String,
+}
+
+impl QtumAddress {
+    /// Builds the `m`-of-`n` multisig redeem script of the keys, in the given order, and its
+    /// script hash address on the configured network.
+    ///
+    /// Keys must be 33-byte compressed or 65-byte uncompressed public keys, both kinds can
+    /// be mixed. Thresholds outside of `1 <= m <= n <= 16` give `InvalidMultisig`.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let key = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    /// let info = QtumAddress::mainnet().multisig_address(1, &[&key]).unwrap();
+    ///
+    /// assert!(info.redeem_script.starts_with("5121"));
+    /// assert!(info.redeem_script.ends_with("51ae"));
+    /// ```
+    pub fn multisig_address(
+        &self,
+        m: u8,
+        pubkeys: &[&[u8]],
+    ) -> Result<MultisigInfo, QtumAddressError> {
+        let n = pubkeys.len();
+        if m == 0 || usize::from(m) > n || n > MAX_MULTISIG_KEYS {
+            return Err(QtumAddressError::InvalidMultisig {
+                required: m,
+                keys: n,
+            });
+        }
+
+        let mut script = Vec::with_capacity(3 + n * 66);
+        script.push(OP_1 - 1 + m);
+        for pubkey in pubkeys {
+            if pubkey.len() != 33 && pubkey.len() != 65 {
+                return Err(QtumAddressError::InvalidPublicKey);
+            }
+            script.push(pubkey.len() as u8);
+            script.extend_from_slice(pubkey);
+        }
+        script.push(OP_1 - 1 + n as u8);
+        script.push(OP_CHECKMULTISIG);
+
+        Ok(MultisigInfo {
+            address: self.p2sh_from_script(&script)?,
+            redeem_script: hex::encode(script),
+        })
+    }
+
+    /// Address paid to by a locking script, for the P2PKH, P2SH, P2PK and witness templates.
+    ///
+    /// Other scripts, `OP_RETURN` outputs included, give `ParsedScript::NonStandard`. Witness
+    /// scripts on `Custom` networks give `UnknownNetwork` since they have no bech32 prefix.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let script = hex::decode("76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac").unwrap();
+    /// let parsed = QtumAddress::testnet().address_from_script(&script).unwrap();
+    ///
+    /// assert_eq!(
+    ///     parsed,
+    ///     ParsedScript::Address {
+    ///         kind: ScriptKind::P2PKH,
+    ///         address: "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".to_string(),
+    ///     }
+    /// );
+    /// ```
+    pub fn address_from_script(&self, script: &[u8]) -> Result<ParsedScript, QtumAddressError> {
+        Ok(match self.script_destination(script)? {
+            Some((kind, address)) => ParsedScript::Address {
+                kind,
+                address: address.to_string(),
+            },
+            None => ParsedScript::NonStandard,
+        })
+    }
+
+    /// Template and typed address of a locking script, `None` for scripts without an address
+    pub(crate) fn script_destination(
+        &self,
+        script: &[u8],
+    ) -> Result<Option<(ScriptKind, TxAddress)>, QtumAddressError> {
+        let base58 = |kind, hash| TxAddress::Base58(Address::new(self.network, kind, hash));
+
+        let destination = match script {
+            [OP_DUP, OP_HASH160, OP_PUSHBYTES_20, hash @ .., OP_EQUALVERIFY, OP_CHECKSIG]
+                if hash.len() == ADDRESS_PAYLOAD_LEN =>
+            {
+                (ScriptKind::P2PKH, base58(AddressType::P2PKH, to_hash(hash)))
+            }
+            [OP_HASH160, OP_PUSHBYTES_20, hash @ .., OP_EQUAL]
+                if hash.len() == ADDRESS_PAYLOAD_LEN =>
+            {
+                (ScriptKind::P2SH, base58(AddressType::P2SH, to_hash(hash)))
+            }
+            [push, pubkey @ .., OP_CHECKSIG]
+                if usize::from(*push) == pubkey.len() && is_pubkey(pubkey) =>
+            {
+                (
+                    ScriptKind::P2PK,
+                    base58(AddressType::P2PKH, hash160(pubkey)),
+                )
+            }
+            [version, push, program @ ..]
+                if usize::from(*push) == program.len() && (2..=40).contains(&program.len()) =>
+            {
+                let (kind, version) = match (*version, program.len()) {
+                    (OP_0, ADDRESS_PAYLOAD_LEN) => (ScriptKind::P2WPKH, 0),
+                    (OP_0, WSH_PROGRAM_LEN) => (ScriptKind::P2WSH, 0),
+                    (OP_1..=OP_16, _) => (ScriptKind::WitnessUnknown, version - OP_1 + 1),
+                    _ => return Ok(None),
+                };
+                // Witness programs only have an address on networks with a bech32 prefix
+                self.from_witness_program(version, program)?;
+                let program = WitnessProgram {
+                    version,
+                    program: program.to_vec(),
+                };
+                (kind, TxAddress::Segwit(self.network, program))
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(destination))
+    }
+
+    /// Locking script paying to an address of the configured network, the inverse of
+    /// `address_from_script`.
+    ///
+    /// Base58 pubkey hash and script hash addresses give the P2PKH and P2SH templates, bech32
+    /// addresses `<version> <program>`. The checksum is always verified, whatever the
+    /// converter's checksum setting.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::mainnet();
+    /// let script = qtum.script_for("qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at0").unwrap();
+    ///
+    /// assert_eq!(hex::encode(script), "0014751e76e8199196d454941c45d1b3a323f1433bd6");
+    /// ```
+    pub fn script_for(&self, address: &str) -> Result<Vec<u8>, QtumAddressError> {
+        let is_bech32 = self.network.bech32_hrp().is_some_and(|hrp| {
+            address.get(..hrp.len() + 1).is_some_and(|prefix| {
+                prefix.as_bytes()[..hrp.len()].eq_ignore_ascii_case(hrp.as_bytes())
+                    && prefix.ends_with('1')
+            })
+        });
+
+        if is_bech32 {
+            let witness = self.decode_witness_program(address)?;
+
+            let mut script = Vec::with_capacity(2 + witness.program.len());
+            script.push(match witness.version {
+                0 => OP_0,
+                version => OP_1 - 1 + version,
+            });
+            script.push(witness.program.len() as u8);
+            script.extend_from_slice(&witness.program);
+            return Ok(script);
+        }
+
+        let payload = decode_address_bytes(address, true)?;
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&payload[1..=ADDRESS_PAYLOAD_LEN]);
+
+        if payload[0] == self.network.to_prefix_byte() {
+            Ok(p2pkh_script(&hash))
+        } else if payload[0] == self.network.to_script_prefix_byte() {
+            Ok(Self::to_p2sh_script(&hash))
+        } else {
+            Err(QtumAddressError::NetworkMismatch {
+                expected: self.network.to_prefix_byte(),
+                found: payload[0],
+            })
+        }
+    }
+
+    /// Locking script of a pay to pubkey hash output:
+    /// `OP_DUP OP_HASH160 <hash160> OP_EQUALVERIFY OP_CHECKSIG`.
+    ///
+    /// The address must be a pubkey hash address of the configured network. As with
+    /// `script_for`, the checksum is always verified, whatever the converter's checksum setting.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::new(QtumNetwork::Testnet);
+    /// let script = qtum.to_p2pkh_script("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+    ///
+    /// assert_eq!(hex::encode(script), "76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac");
+    /// ```
+    pub fn to_p2pkh_script(&self, address: &str) -> Result<Vec<u8>, QtumAddressError> {
+        let payload = decode_address_bytes(address, true)?;
+
+        match self.kind_of_version(payload[0]) {
+            Some(AddressType::P2PKH) => {}
+            Some(found) => {
+                return Err(QtumAddressError::WrongAddressType {
+                    expected: AddressType::P2PKH,
+                    found,
+                })
+            }
+            None => {
+                return Err(QtumAddressError::NetworkMismatch {
+                    expected: self.network.to_prefix_byte(),
+                    found: payload[0],
+                })
+            }
+        }
+
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&payload[1..=ADDRESS_PAYLOAD_LEN]);
+
+        Ok(p2pkh_script(&hash))
+    }
+
+    /// Script hash address of a redeem script on the configured network, the hash160 of the
+    /// script with the script hash version byte.
+    ///
+    /// Empty scripts give `EmptyInput`, scripts over `MAX_REDEEM_SCRIPT_LEN` bytes `ScriptTooLong`.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// // OP_TRUE
+    /// let addr = QtumAddress::mainnet().p2sh_from_script(&[0x51]).unwrap();
+    /// assert_eq!(addr, "MTnKRHunzrvFDTK5okuZyrwPjWNnTSRjZi");
+    /// ```
+    pub fn p2sh_from_script(&self, script: &[u8]) -> Result<Base58Address, QtumAddressError> {
+        if script.is_empty() {
+            return Err(QtumAddressError::EmptyInput);
+        }
+        if script.len() > MAX_REDEEM_SCRIPT_LEN {
+            return Err(QtumAddressError::ScriptTooLong {
+                max: MAX_REDEEM_SCRIPT_LEN,
+                found: script.len(),
+            });
+        }
+
+        Ok(self.from_script_hash(&hash160(script)))
+    }
+
+    /// Locking script of a pay to script hash output: `OP_HASH160 <script hash> OP_EQUAL`
+    pub fn to_p2sh_script(script_hash: &[u8; ADDRESS_PAYLOAD_LEN]) -> Vec<u8> {
+        let mut script = Vec::with_capacity(ADDRESS_PAYLOAD_LEN + 3);
+        script.extend_from_slice(&[OP_HASH160, OP_PUSHBYTES_20]);
+        script.extend_from_slice(script_hash);
+        script.push(OP_EQUAL);
+
+        script
+    }
+}
+
+/// `OP_DUP OP_HASH160 <hash160> OP_EQUALVERIFY OP_CHECKSIG`
+pub(crate) fn p2pkh_script(hash: &[u8; ADDRESS_PAYLOAD_LEN]) -> Vec<u8> {
+    let mut script = Vec::with_capacity(ADDRESS_PAYLOAD_LEN + 5);
+    script.extend_from_slice(&[OP_DUP, OP_HASH160, OP_PUSHBYTES_20]);
+    script.extend_from_slice(hash);
+    script.extend_from_slice(&[OP_EQUALVERIFY, OP_CHECKSIG]);
+
+    script
+}
+
+/// Hash of a script template, the caller has checked the length
+fn to_hash(bytes: &[u8]) -> [u8; ADDRESS_PAYLOAD_LEN] {
+    let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+    hash.copy_from_slice(bytes);
+    hash
+}
+
+/// Data pushed by a script made only of push opcodes, `None` if any other opcode appears.
+///
+/// `OP_0` pushes an empty item, a push longer than the rest of the script gives
+/// `InvalidScript`.
+pub(crate) fn push_data(script: &[u8]) -> Result<Option<Vec<&[u8]>>, QtumAddressError> {
+    let mut pushes = Vec::new();
+    let mut offset = 0;
+
+    while let Some(opcode) = script.get(offset) {
+        let (header, len) = match *opcode {
+            OP_0..=OP_PUSHBYTES_75 => (1, Some(usize::from(*opcode))),
+            OP_PUSHDATA1 => (2, script.get(offset + 1).map(|len| usize::from(*len))),
+            OP_PUSHDATA2 => (
+                3,
+                script
+                    .get(offset + 1..offset + 3)
+                    .map(|len| usize::from(u16::from_le_bytes([len[0], len[1]]))),
+            ),
+            OP_PUSHDATA4 => (
+                5,
+                script
+                    .get(offset + 1..offset + 5)
+                    .map(|len| u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize),
+            ),
+            _ => return Ok(None),
+        };
+
+        let data = len
+            .and_then(|len| script.get(offset + header..(offset + header).checked_add(len)?))
+            .ok_or(QtumAddressError::InvalidScript { offset })?;
+        pushes.push(data);
+        offset += header + data.len();
+    }
+
+    Ok(Some(pushes))
+}
+
+/// Size and prefix check of a SEC1 public key, the point itself is not validated
+pub(crate) fn is_pubkey(bytes: &[u8]) -> bool {
+    match bytes.len() {
+        33 => bytes[0] == 0x02 || bytes[0] == 0x03,
+        65 => bytes[0] == 0x04,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OP_0;
+    use crate::*;
+    use alloc::vec;
+
+    fn parse(qtum: QtumAddress, script: &str) -> ParsedScript {
+        qtum.address_from_script(&hex::decode(script).unwrap())
+            .unwrap()
+    }
+
+    fn address(kind: ScriptKind, address: &str) -> ParsedScript {
+        ParsedScript::Address {
+            kind,
+            address: address.to_string(),
+        }
+    }
+
+    #[test]
+    fn standard_scripts() {
+        let mainnet = QtumAddress::mainnet();
+
+        assert_eq!(
+            parse(
+                QtumAddress::testnet(),
+                "76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac"
+            ),
+            address(ScriptKind::P2PKH, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+        );
+        assert_eq!(
+            parse(mainnet, "a914da1745e9b549bd0bfa1a569971c77eba30cd5a4b87"),
+            address(ScriptKind::P2SH, "MTnKRHunzrvFDTK5okuZyrwPjWNnTSRjZi")
+        );
+        assert_eq!(
+            parse(
+                mainnet,
+                "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac"
+            ),
+            address(ScriptKind::P2PK, "QXHFfTBKYXjaaTH1e7Rox8CcdNPGHVhM59")
+        );
+        assert_eq!(
+            parse(
+                mainnet,
+                "410479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8ac"
+            ),
+            address(ScriptKind::P2PK, "QZtMgPgmVSujMWKykPTbtVqgE1URC3ibGT")
+        );
+        assert_eq!(
+            parse(mainnet, "0014751e76e8199196d454941c45d1b3a323f1433bd6"),
+            address(
+                ScriptKind::P2WPKH,
+                "qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at0"
+            )
+        );
+        assert_eq!(
+            parse(
+                mainnet,
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"
+            ),
+            address(
+                ScriptKind::P2WSH,
+                "qc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qtd3g7a"
+            )
+        );
+        assert_eq!(
+            parse(
+                mainnet,
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            ),
+            address(
+                ScriptKind::WitnessUnknown,
+                "qc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq3rvpqr"
+            )
+        );
+    }
+
+    #[test]
+    fn standard_scripts_through_p2sh_converter() {
+        let scripts = [
+            "76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac",
+            "a914da1745e9b549bd0bfa1a569971c77eba30cd5a4b87",
+            "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+        ];
+
+        for network in &[QtumNetwork::Mainnet, QtumNetwork::Testnet] {
+            for script in scripts.iter() {
+                assert_eq!(
+                    parse(QtumAddress::new_p2sh(*network), script),
+                    parse(QtumAddress::new(*network), script)
+                );
+            }
+        }
+        assert_eq!(
+            parse(
+                QtumAddress::new_p2sh(QtumNetwork::Testnet),
+                "76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac"
+            ),
+            address(ScriptKind::P2PKH, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+        );
+    }
+
+    #[test]
+    fn non_standard_scripts() {
+        let mainnet = QtumAddress::mainnet();
+
+        for script in &[
+            // OP_RETURN "hello"
+            "6a0568656c6c6f",
+            // Contract call: version, gas limit, gas price, data, contract, OP_CALL
+            "010403400d030128044e70b1dc146c89a1a6ca2ae7c00b248bb2832d6f480f27da68c2",
+            // Truncated P2PKH
+            "76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888",
+            // P2PK with an invalid key prefix
+            "210579be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+            // Version 0 program of 25 bytes
+            "0019751e76e8199196d454941c45d1b3a323f1433bd6751e76e819",
+            "",
+        ] {
+            assert_eq!(parse(mainnet, script), ParsedScript::NonStandard);
+        }
+    }
+
+    #[test]
+    fn script_for_round_trips_through_parser() {
+        let cases: &[(QtumAddress, &str)] = &[
+            (
+                QtumAddress::testnet(),
+                "76a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac",
+            ),
+            (
+                QtumAddress::mainnet(),
+                "a914da1745e9b549bd0bfa1a569971c77eba30cd5a4b87",
+            ),
+            (
+                QtumAddress::mainnet(),
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                QtumAddress::testnet(),
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                QtumAddress::new(QtumNetwork::Regtest),
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+
+        for (qtum, script) in cases {
+            let script = hex::decode(script).unwrap();
+            let address = match qtum.address_from_script(&script).unwrap() {
+                ParsedScript::Address { address, .. } => address,
+                ParsedScript::NonStandard => panic!("standard script"),
+            };
+
+            assert_eq!(qtum.script_for(&address).unwrap(), script);
+        }
+
+        assert_eq!(
+            QtumAddress::mainnet()
+                .script_for("QC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KQ52AT0")
+                .unwrap(),
+            hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+        );
+    }
+
+    #[test]
+    fn script_for_rejects_invalid_addresses() {
+        let qtum = QtumAddress::builder()
+            .network(QtumNetwork::Testnet)
+            .strict_checksum(false)
+            .build();
+
+        assert_eq!(
+            qtum.script_for("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            qtum.script_for("tq1qw508d6qejxtdg4y5r3zarvary0c5xw7kzztr6g"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            QtumAddress::mainnet().script_for("qé1"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 1,
+                character: 'é'
+            })
+        );
+        assert_eq!(
+            QtumAddress::mainnet().script_for("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+    }
+
+    #[test]
+    fn script_round_trip() {
+        let qtum = QtumAddress::testnet();
+        let script = qtum
+            .to_p2pkh_script("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+            .unwrap();
+        assert_eq!(
+            qtum.address_from_script(&script).unwrap(),
+            address(ScriptKind::P2PKH, "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+        );
+
+        let script = QtumAddress::to_p2sh_script(&[0x11; 20]);
+        assert_eq!(
+            qtum.address_from_script(&script).unwrap(),
+            address(
+                ScriptKind::P2SH,
+                qtum.from_script_hash(&[0x11; 20]).as_str()
+            )
+        );
+
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Custom(0x1f)).address_from_script(
+                &hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+            ),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x1f })
+        );
+    }
+
+    #[test]
+    fn p2pkh_script_layout() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let script = qtum
+            .to_p2pkh_script("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+            .unwrap();
+
+        assert_eq!(script.len(), 25);
+        assert_eq!(&script[..3], &[0x76, 0xa9, 0x14]);
+        assert_eq!(
+            hex::encode(&script[3..23]),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+        assert_eq!(&script[23..], &[0x88, 0xac]);
+    }
+
+    #[test]
+    fn p2pkh_script_rejects_other_addresses() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+        let p2sh = QtumAddress::new_p2sh(QtumNetwork::Testnet)
+            .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap();
+
+        for converter in &[qtum, QtumAddress::new_p2sh(QtumNetwork::Testnet)] {
+            assert_eq!(
+                converter.to_p2pkh_script(&p2sh),
+                Err(QtumAddressError::WrongAddressType {
+                    expected: AddressType::P2PKH,
+                    found: AddressType::P2SH
+                })
+            );
+        }
+        assert_eq!(
+            QtumAddress::mainnet().to_p2pkh_script("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+        assert_eq!(
+            qtum.to_p2pkh_script("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            QtumAddress::builder()
+                .network(QtumNetwork::Testnet)
+                .strict_checksum(false)
+                .build()
+                .to_p2pkh_script("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn p2sh_from_op_true_script() {
+        // hash160(OP_TRUE) = da1745e9b549bd0bfa1a569971c77eba30cd5a4b, the same script hash as
+        // bitcoin's 3MaB7QVq3k4pQx3BhsvEADgzQonLSBwMdj
+        let script = [0x51];
+
+        let mainnet = QtumAddress::mainnet().p2sh_from_script(&script).unwrap();
+        assert_eq!(mainnet, "MTnKRHunzrvFDTK5okuZyrwPjWNnTSRjZi");
+        assert_eq!(
+            QtumAddress::new_p2sh(QtumNetwork::Mainnet)
+                .gethexaddress(&mainnet)
+                .unwrap(),
+            "da1745e9b549bd0bfa1a569971c77eba30cd5a4b"
+        );
+
+        let testnet = QtumAddress::testnet().p2sh_from_script(&script).unwrap();
+        assert_eq!(testnet, "mc4WVoo5bgipHTgHGvth5PGbVmqPAcVNci");
+        assert_eq!(
+            QtumAddress::new_p2sh(QtumNetwork::Testnet).p2sh_from_script(&script),
+            Ok(testnet)
+        );
+    }
+
+    #[test]
+    fn p2sh_from_script_limits() {
+        let qtum = QtumAddress::mainnet();
+
+        assert_eq!(
+            qtum.p2sh_from_script(&[]),
+            Err(QtumAddressError::EmptyInput)
+        );
+        assert!(qtum.p2sh_from_script(&[0x51; 520]).is_ok());
+        assert_eq!(
+            qtum.p2sh_from_script(&[0x51; 521]),
+            Err(QtumAddressError::ScriptTooLong {
+                max: 520,
+                found: 521
+            })
+        );
+    }
+
+    const KEY_1: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const KEY_2: &str = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+    const KEY_1_UNCOMPRESSED: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn multisig_2_of_3_mixed_keys() {
+        let keys: Vec<Vec<u8>> = [KEY_1, KEY_2, KEY_1_UNCOMPRESSED]
+            .iter()
+            .map(|key| hex::decode(key).unwrap())
+            .collect();
+        let keys: Vec<&[u8]> = keys.iter().map(|key| &key[..]).collect();
+
+        let info = QtumAddress::mainnet().multisig_address(2, &keys).unwrap();
+        assert_eq!(
+            info.redeem_script,
+            format!("5221{}21{}41{}53ae", KEY_1, KEY_2, KEY_1_UNCOMPRESSED)
+        );
+        assert_eq!(info.address, "M8Y8VQxjrDiTQiV57c3B8MxuF76n4RdCP9");
+        assert_eq!(
+            QtumAddress::mainnet().p2sh_from_script(&hex::decode(&info.redeem_script).unwrap()),
+            Ok(info.address.clone())
+        );
+
+        let testnet = QtumAddress::testnet().multisig_address(2, &keys).unwrap();
+        assert_eq!(testnet.address, "mGpKZvr2T3X2UirGan2JDtJ71NZNk4bA4j");
+        assert_eq!(testnet.redeem_script, info.redeem_script);
+    }
+
+    /// Compressed keys of a multisig redeem script, in order
+    fn multisig_keys(script: &[u8]) -> Vec<&[u8]> {
+        script[1..script.len() - 2]
+            .chunks(34)
+            .map(|push| &push[1..])
+            .collect()
+    }
+
+    #[test]
+    fn multisig_published_vectors() {
+        // 5-of-7 of rust-bitcoin's `test_p2sh_parse`, a Bitcoin testnet script hash address
+        let script = hex::decode("552103a765fc35b3f210b95223846b36ef62a4e53e34e2925270c2c7906b92c9f718eb2103c327511374246759ec8d0b89fa6c6b23b33e11f92c5bc155409d86de0c79180121038cae7406af1f12f4786d820a1466eec7bc5785a1b5e4a387eca6d797753ef6db2103252bfb9dcaab0cd00353f2ac328954d791270203d66c2be8b430f115f451b8a12103e79412d42372c55dd336f2eb6eb639ef9d74a22041ba79382c74da2338fe58ad21035049459a4ebc00e876a9eef02e72a3e70202d3d1f591fc0dd542f93f642021f82102016f682920d9723c61b27f562eb530c926c00106004798b6471e8c52c60ee02057ae").unwrap();
+        let info = QtumAddress::new(QtumNetwork::Custom(0xc4))
+            .multisig_address(5, &multisig_keys(&script))
+            .unwrap();
+        assert_eq!(info.redeem_script, hex::encode(&script));
+        assert_eq!(info.address, "2N3zXjbwdTcPsJiy8sUK9FhWJhqQCxA8Jjr");
+
+        // 2-of-3 witness script spent by Bitcoin mainnet transaction
+        // 5df912fda4becb1c29e928bec8d64d93e9ba8efa9b5b405bd683c86fd2c65667, its hash is the
+        // program of bc1qwqdg6squsna38e46795at95yu9atm8azzmyvckulcc7kytlcckxswvvzej
+        let script = hex::decode("52210375e00eb72e29da82b89367947f29ef34afb75e8654f6ea368e0acdfd92976b7c2103a1b26313f430c4b15bb1fdce663207659d8cac749a0e53d70eff01874496feff2103c96d495bfdd5ba4145e3e046fee45e84a8a48ad05bd8dbb395c011a32cf9f88053ae").unwrap();
+        let info = QtumAddress::mainnet()
+            .multisig_address(2, &multisig_keys(&script))
+            .unwrap();
+        assert_eq!(info.redeem_script, hex::encode(&script));
+        assert_eq!(
+            hex::encode(hashes::sha256(&script)),
+            "701a8d401c84fb13e6baf169d59684e17abd9fa216c8cc5b9fc63d622ff8c58d"
+        );
+
+        // 2-of-2 witness script spent through P2SH by Bitcoin mainnet transaction
+        // f9ee2be4df05041d0e0a35d7caa3157495ca4f93b233234c9967b6901dacf7a9
+        let script = hex::decode("522103e5529d8eaa3d559903adb2e881eb06c86ac2574ffa503c45f4e942e2a693b33e2102e5f10fcdcdbab211e0af6a481f5532536ec61a5fdbf7183770cf8680fe729d8152ae").unwrap();
+        let info = QtumAddress::mainnet()
+            .multisig_address(2, &multisig_keys(&script))
+            .unwrap();
+        assert_eq!(info.redeem_script, hex::encode(&script));
+        let nested = [&[OP_0, 0x20][..], &hashes::sha256(&script)[..]].concat();
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Custom(0x05))
+                .p2sh_from_script(&nested)
+                .unwrap(),
+            "36EqgNnsWW94SreZgBWc1ANC6wpFZwirHr"
+        );
+    }
+
+    #[test]
+    fn multisig_limits() {
+        let qtum = QtumAddress::mainnet();
+        let key = hex::decode(KEY_1).unwrap();
+
+        for (m, n) in &[(0u8, 1usize), (2, 1), (1, 0), (1, 17)] {
+            let keys = vec![&key[..]; *n];
+            assert_eq!(
+                qtum.multisig_address(*m, &keys),
+                Err(QtumAddressError::InvalidMultisig {
+                    required: *m,
+                    keys: *n
+                })
+            );
+        }
+
+        // 15 compressed keys are the most that fit in a redeem script
+        let keys = [&key[..]; 15];
+        let info = qtum.multisig_address(15, &keys).unwrap();
+        assert!(info.redeem_script.starts_with("5f21"));
+        assert!(info.redeem_script.ends_with("5fae"));
+        assert_eq!(
+            qtum.multisig_address(16, &[&key[..]; 16]),
+            Err(QtumAddressError::ScriptTooLong {
+                max: 520,
+                found: 3 + 16 * 34
+            })
+        );
+
+        assert_eq!(
+            qtum.multisig_address(1, &[&key[..32]]),
+            Err(QtumAddressError::InvalidPublicKey)
+        );
+
+        let uncompressed = hex::decode(KEY_1_UNCOMPRESSED).unwrap();
+        assert_eq!(
+            qtum.multisig_address(1, &[&uncompressed[..]; 8]),
+            Err(QtumAddressError::ScriptTooLong {
+                max: 520,
+                found: 3 + 8 * 66
+            })
+        );
+    }
+
+    #[test]
+    fn p2sh_script_layout() {
+        let script = QtumAddress::to_p2sh_script(&[0x11; 20]);
+
+        assert_eq!(script.len(), 23);
+        assert_eq!(&script[..2], &[0xa9, 0x14]);
+        assert_eq!(&script[2..22], &[0x11; 20]);
+        assert_eq!(script[22], 0x87);
+    }
+}
@@ -0,0 +1,659 @@
+//! Native segwit addresses, bech32 and bech32m encoded as described in BIP173 and BIP350
+
+use crate::hashes::sha256;
+use crate::{
+    sanitize, HexAddress, QtumAddress, QtumAddressError, QtumNetwork, ToHexAddress,
+    ADDRESS_PAYLOAD_LEN,
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Characters of the bech32 alphabet, indexed by their 5-bit value
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Generator of the BCH checksum
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+/// Constant the checksum of a valid bech32 string evaluates to, used by witness version 0
+const BECH32_CONST: u32 = 1;
+
+/// Constant the checksum of a valid bech32m string evaluates to, used by witness versions 1+
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Separator between the human readable part and the data
+const SEPARATOR: char = '1';
+
+/// Number of checksum characters at the end of the data part
+const CHECKSUM_CHARS: usize = 6;
+
+/// Longest bech32 string allowed by BIP173
+pub const MAX_BECH32_LEN: usize = 90;
+
+/// Highest witness version
+pub const MAX_WITNESS_VERSION: u8 = 16;
+
+/// Length of a version 0 pay to witness script hash program
+pub const WSH_PROGRAM_LEN: usize = 32;
+
+/// Witness version and program of a native segwit address
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct WitnessProgram {
+    /// Witness version, 0 to 16
+    pub version: u8,
+    /// Witness program, 2 to 40 bytes
+    pub program: Vec<u8>,
+}
+
+impl WitnessProgram {
+    /// Whether this is a version 0 pay to witness pubkey hash program
+    pub fn is_p2wpkh(&self) -> bool {
+        self.version == 0 && self.program.len() == ADDRESS_PAYLOAD_LEN
+    }
+
+    /// Whether this is a version 0 pay to witness script hash program
+    pub fn is_p2wsh(&self) -> bool {
+        self.version == 0 && self.program.len() == WSH_PROGRAM_LEN
+    }
+
+    /// SHA256 of the witness script of a pay to witness script hash program, `None` for
+    /// other programs
+    pub fn script_hash(&self) -> Option<[u8; WSH_PROGRAM_LEN]> {
+        if !self.is_p2wsh() {
+            return None;
+        }
+
+        let mut hash = [0u8; WSH_PROGRAM_LEN];
+        hash.copy_from_slice(&self.program);
+        Some(hash)
+    }
+
+    /// Hash160 of the public key of a version 0 pay to witness pubkey hash program, the same
+    /// hash as in the legacy address of the key.
+    ///
+    /// Programs of other versions or lengths give `None`.
+    pub fn pubkey_hash(&self) -> Option<HexAddress> {
+        if !self.is_p2wpkh() {
+            return None;
+        }
+
+        let mut hash = [0u8; ADDRESS_PAYLOAD_LEN];
+        hash.copy_from_slice(&self.program);
+        Some(HexAddress::from(hash))
+    }
+}
+
+impl ToHexAddress for WitnessProgram {
+    /// The pubkey hash of a P2WPKH program, any other program gives
+    /// `UnrepresentableWitnessProgram` instead of being truncated
+    fn to_hex_address(&self, _network: QtumNetwork) -> Result<HexAddress, QtumAddressError> {
+        self.pubkey_hash()
+            .ok_or(QtumAddressError::UnrepresentableWitnessProgram {
+                version: self.version,
+                length: self.program.len(),
+            })
+    }
+}
+
+impl QtumAddress {
+    /// Pay to witness script hash address of a witness script: the single SHA256 of the script
+    /// as a version 0 program, bech32 encoded for the configured network.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::mainnet();
+    /// let addr = qtum.p2wsh_from_script(&[0x51]).unwrap();
+    ///
+    /// assert!(qtum.decode_witness_program(&addr).unwrap().is_p2wsh());
+    /// ```
+    pub fn p2wsh_from_script(&self, script: &[u8]) -> Result<String, QtumAddressError> {
+        if script.is_empty() {
+            return Err(QtumAddressError::EmptyInput);
+        }
+
+        self.from_witness_program(0, &sha256(script))
+    }
+
+    /// Encoding a witness program as a bech32 address of the configured network, or bech32m
+    /// for witness versions 1 and above.
+    ///
+    /// Versions above 16, programs outside of 2 to 40 bytes and version 0 programs that are
+    /// neither 20 nor 32 bytes give `InvalidWitnessProgram`. `Custom` networks have no bech32
+    /// prefix and give `UnknownNetwork`.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let hash = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+    /// let addr = QtumAddress::mainnet().from_witness_program(0, &hash).unwrap();
+    ///
+    /// assert_eq!(addr, "qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at0");
+    /// ```
+    pub fn from_witness_program(
+        &self,
+        version: u8,
+        program: &[u8],
+    ) -> Result<String, QtumAddressError> {
+        let hrp = hrp(self.network())?;
+        check_program(version, program.len())?;
+
+        let mut data = Vec::with_capacity(1 + (program.len() * 8 + 4) / 5 + CHECKSUM_CHARS);
+        data.push(version);
+        data.extend(convert_bits(program, 8, 5, true).expect("padding is allowed"));
+        data.extend_from_slice(&create_checksum(hrp, &data, checksum_const(version)));
+
+        let mut address = String::with_capacity(hrp.len() + 1 + data.len());
+        address.push_str(hrp);
+        address.push(SEPARATOR);
+        address.extend(data.iter().map(|d| CHARSET[*d as usize] as char));
+
+        Ok(address)
+    }
+
+    /// Decoding a bech32 address of the configured network into its witness program.
+    ///
+    /// The checksum, the all lowercase or all uppercase rule and the witness program rules of
+    /// BIP173 are enforced. Version 0 programs must use the bech32 checksum and later versions
+    /// the bech32m one, versions without a known template are returned as they are.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let witness = QtumAddress::mainnet()
+    ///     .decode_witness_program("qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at0")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(witness.version, 0);
+    /// assert_eq!(
+    ///     witness.pubkey_hash().unwrap(),
+    ///     "751e76e8199196d454941c45d1b3a323f1433bd6"
+    /// );
+    /// ```
+    pub fn decode_witness_program(
+        &self,
+        address: &str,
+    ) -> Result<WitnessProgram, QtumAddressError> {
+        let expected = hrp(self.network())?;
+        let (hrp, data) = decode_bech32(address)?;
+
+        if hrp != expected {
+            return Err(QtumAddressError::HrpMismatch {
+                expected,
+                found: hrp,
+            });
+        }
+
+        let (data, checksum) = data.split_at(data.len() - CHECKSUM_CHARS);
+        if data.is_empty() {
+            return Err(QtumAddressError::InvalidBech32);
+        }
+
+        let version = data[0];
+        if create_checksum(&hrp, data, checksum_const(version))[..] != *checksum {
+            return Err(QtumAddressError::InvalidChecksum);
+        }
+
+        let program =
+            convert_bits(&data[1..], 5, 8, false).ok_or(QtumAddressError::InvalidBech32)?;
+        check_program(version, program.len())?;
+
+        Ok(WitnessProgram { version, program })
+    }
+}
+
+/// Bech32 prefix of a network
+fn hrp(network: QtumNetwork) -> Result<&'static str, QtumAddressError> {
+    network
+        .params_or_prefix()
+        .map(|params| params.bech32_hrp)
+        .map_err(|prefix| QtumAddressError::UnknownNetwork { prefix })
+}
+
+/// Checksum constant of a witness version
+fn checksum_const(version: u8) -> u32 {
+    if version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    }
+}
+
+/// Witness version and program length rules of BIP141
+fn check_program(version: u8, length: usize) -> Result<(), QtumAddressError> {
+    let valid = match version {
+        0 => length == ADDRESS_PAYLOAD_LEN || length == WSH_PROGRAM_LEN,
+        1..=MAX_WITNESS_VERSION => (2..=40).contains(&length),
+        _ => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(QtumAddressError::InvalidWitnessProgram { version, length })
+    }
+}
+
+/// Splitting a bech32 string into its lowercase human readable part and the 5-bit values of
+/// its data part, checksum included but not verified
+fn decode_bech32(address: &str) -> Result<(String, Vec<u8>), QtumAddressError> {
+    if address.is_empty() {
+        return Err(QtumAddressError::EmptyInput);
+    }
+
+    sanitize::check_characters(address, MAX_BECH32_LEN + 1)?;
+
+    if address.len() > MAX_BECH32_LEN {
+        return Err(QtumAddressError::TooLong {
+            max: MAX_BECH32_LEN,
+            found: address.len(),
+        });
+    }
+
+    if let Some((position, character)) = address
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii() || !(33..=126).contains(&(*c as u8)))
+    {
+        return Err(QtumAddressError::InvalidCharacter {
+            position,
+            character,
+        });
+    }
+
+    let has_lower = address.bytes().any(|b| b.is_ascii_lowercase());
+    let has_upper = address.bytes().any(|b| b.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(QtumAddressError::InvalidBech32);
+    }
+
+    let address = address.to_ascii_lowercase();
+    let separator = match address.rfind(SEPARATOR) {
+        Some(separator) if separator > 0 && address.len() - separator > CHECKSUM_CHARS => separator,
+        _ => return Err(QtumAddressError::InvalidBech32),
+    };
+
+    let mut data = Vec::with_capacity(address.len() - separator - 1);
+    for (position, character) in address.char_indices().skip(separator + 1) {
+        match CHARSET.iter().position(|c| *c as char == character) {
+            Some(value) => data.push(value as u8),
+            None => {
+                return Err(QtumAddressError::InvalidCharacter {
+                    position,
+                    character,
+                })
+            }
+        }
+    }
+
+    Ok((address[..separator].to_string(), data))
+}
+
+/// BCH checksum over the expanded human readable part and the data
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    values.fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = (chk & 0x01ff_ffff) << 5 ^ u32::from(value);
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, g)| chk ^ g)
+    })
+}
+
+/// Six checksum characters of the data, as 5-bit values
+fn create_checksum(hrp: &str, data: &[u8], constant: u32) -> [u8; CHECKSUM_CHARS] {
+    let values = hrp
+        .bytes()
+        .map(|b| b >> 5)
+        .chain(Some(0))
+        .chain(hrp.bytes().map(|b| b & 0x1f))
+        .chain(data.iter().copied())
+        .chain([0u8; CHECKSUM_CHARS].iter().copied());
+    let modulus = polymod(values) ^ constant;
+
+    let mut checksum = [0u8; CHECKSUM_CHARS];
+    for (i, value) in checksum.iter_mut().enumerate() {
+        *value = ((modulus >> (5 * (5 - i))) & 0x1f) as u8;
+    }
+    checksum
+}
+
+/// Regrouping bits, `None` when the leftover bits are not valid zero padding
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max = (1u32 << to) - 1;
+    let mut converted = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for value in data {
+        acc = (acc << from) | u32::from(*value);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            converted.push(((acc >> bits) & max) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            converted.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+
+    Some(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// Hash160 of the compressed public key of the private key 1
+    const PUBKEY_HASH: &str = "751e76e8199196d454941c45d1b3a323f1433bd6";
+
+    const MAINNET_P2WPKH: &str = "qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at0";
+    const TESTNET_P2WPKH: &str = "tq1qw508d6qejxtdg4y5r3zarvary0c5xw7kzztr6f";
+
+    /// SHA256 of `WITNESS_SCRIPT`
+    const SCRIPT_SHA256: &str = "9a795669dc3bdb8a3d357228fcbaab506f1dd4cc336d9d28cedfe009e7bb40b7";
+    const MAINNET_P2WSH: &str = "qc1qnfu4v6wu80dc50f4wg50ew4t2ph3m4xvxdke62xwmlsqneamgzmsswl9du";
+    const TESTNET_P2WSH: &str = "tq1qnfu4v6wu80dc50f4wg50ew4t2ph3m4xvxdke62xwmlsqneamgzms8mkuck";
+
+    #[test]
+    fn p2wpkh_round_trip() {
+        let hash = hex::decode(PUBKEY_HASH).unwrap();
+
+        for (qtum, address) in &[
+            (QtumAddress::mainnet(), MAINNET_P2WPKH),
+            (QtumAddress::testnet(), TESTNET_P2WPKH),
+            (
+                QtumAddress::new(QtumNetwork::Regtest),
+                "qcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7k28q8nz",
+            ),
+        ] {
+            assert_eq!(qtum.from_witness_program(0, &hash).unwrap(), *address);
+
+            let witness = qtum.decode_witness_program(address).unwrap();
+            assert_eq!(witness.version, 0);
+            assert_eq!(witness.program, hash);
+            assert_eq!(witness.pubkey_hash().unwrap(), PUBKEY_HASH);
+        }
+
+        // The program is the hash of the legacy address of the same key
+        let legacy = QtumAddress::testnet()
+            .gethexaddress("qUEeiBfBZiTuHKvPA85a1u5PeeMkLnNF3K")
+            .unwrap();
+        assert_eq!(
+            QtumAddress::testnet()
+                .decode_witness_program(TESTNET_P2WPKH)
+                .unwrap()
+                .pubkey_hash(),
+            Some(legacy)
+        );
+    }
+
+    #[test]
+    fn bitcoin_vector_encodes_with_bitcoin_hrp() {
+        // BIP173 P2WPKH vector, only the prefix and therefore the checksum differ
+        let hash = hex::decode(PUBKEY_HASH).unwrap();
+        let bitcoin = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let qtum = QtumAddress::mainnet()
+            .from_witness_program(0, &hash)
+            .unwrap();
+
+        assert_eq!(qtum[3..qtum.len() - 6], bitcoin[3..bitcoin.len() - 6]);
+    }
+
+    #[test]
+    fn uppercase_is_accepted_mixed_case_is_not() {
+        let qtum = QtumAddress::mainnet();
+
+        assert_eq!(
+            qtum.decode_witness_program(&MAINNET_P2WPKH.to_uppercase())
+                .unwrap()
+                .pubkey_hash()
+                .unwrap(),
+            PUBKEY_HASH
+        );
+        assert_eq!(
+            qtum.decode_witness_program("qc1qW508d6qejxtdg4y5r3zarvary0c5xw7kq52at0"),
+            Err(QtumAddressError::InvalidBech32)
+        );
+    }
+
+    #[test]
+    fn corrupted_checksum_is_rejected() {
+        assert_eq!(
+            QtumAddress::mainnet()
+                .decode_witness_program("qc1qw508d6qejxtdg4y5r3zarvary0c5xw7kq52at2"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn invalid_addresses() {
+        let qtum = QtumAddress::mainnet();
+
+        assert_eq!(
+            qtum.decode_witness_program(TESTNET_P2WPKH),
+            Err(QtumAddressError::HrpMismatch {
+                expected: "qc",
+                found: "tq".to_string()
+            })
+        );
+        assert_eq!(
+            qtum.decode_witness_program("qc1qw508d6qejxtdg4y5r3zarvary0c5xw7bq52at0"),
+            Err(QtumAddressError::InvalidCharacter {
+                position: 35,
+                character: 'b'
+            })
+        );
+        assert_eq!(
+            qtum.decode_witness_program("qc1at0"),
+            Err(QtumAddressError::InvalidBech32)
+        );
+        assert_eq!(
+            qtum.decode_witness_program(""),
+            Err(QtumAddressError::EmptyInput)
+        );
+        assert_eq!(
+            qtum.decode_witness_program(&format!("qc1{}", "q".repeat(88))),
+            Err(QtumAddressError::TooLong { max: 90, found: 91 })
+        );
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Custom(0x1f)).decode_witness_program(MAINNET_P2WPKH),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x1f })
+        );
+    }
+
+    #[test]
+    fn witness_program_rules() {
+        let qtum = QtumAddress::mainnet();
+
+        assert_eq!(
+            qtum.from_witness_program(0, &[0u8; 21]),
+            Err(QtumAddressError::InvalidWitnessProgram {
+                version: 0,
+                length: 21
+            })
+        );
+        assert_eq!(
+            qtum.from_witness_program(17, &[0u8; 20]),
+            Err(QtumAddressError::InvalidWitnessProgram {
+                version: 17,
+                length: 20
+            })
+        );
+        assert_eq!(
+            qtum.from_witness_program(1, &[0u8; 41]),
+            Err(QtumAddressError::InvalidWitnessProgram {
+                version: 1,
+                length: 41
+            })
+        );
+
+        let script_hash = [0x42u8; 32];
+        let p2wsh = qtum.from_witness_program(0, &script_hash).unwrap();
+        let witness = qtum.decode_witness_program(&p2wsh).unwrap();
+        assert_eq!(witness.program, script_hash);
+        assert_eq!(witness.pubkey_hash(), None);
+    }
+
+    /// BIP350 vectors of witness versions 1, 2 and 16 with the Qtum prefixes:
+    /// version, program and the mainnet, testnet and regtest addresses
+    const BECH32M_VECTORS: &[(u8, &str, [&str; 3])] = &[
+        (
+            1,
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            [
+                "qc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq3rvpqr",
+                "tq1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqxk9c4f",
+                "qcrt1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqsjn9ns",
+            ],
+        ),
+        (
+            1,
+            "751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+            [
+                "qc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7ksv4lma",
+                "tq1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kcz5p36",
+                "qcrt1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kkxxfuu",
+            ],
+        ),
+        (
+            16,
+            "751e",
+            ["qc1sw50qveugzg", "tq1sw50qexjvtq", "qcrt1sw50q6v4egv"],
+        ),
+        (
+            2,
+            "751e76e8199196d454941c45d1b3a323",
+            [
+                "qc1zw508d6qejxtdg4y5r3zarvaryva2um28",
+                "tq1zw508d6qejxtdg4y5r3zarvaryvjayn6x",
+                "qcrt1zw508d6qejxtdg4y5r3zarvaryvp2gq6w",
+            ],
+        ),
+    ];
+
+    #[test]
+    fn bech32m_round_trip() {
+        let networks = [
+            QtumNetwork::Mainnet,
+            QtumNetwork::Testnet,
+            QtumNetwork::Regtest,
+        ];
+
+        for (version, program, addresses) in BECH32M_VECTORS {
+            let program = hex::decode(program).unwrap();
+
+            for (network, address) in networks.iter().zip(addresses.iter()) {
+                let qtum = QtumAddress::new(*network);
+                assert_eq!(
+                    qtum.from_witness_program(*version, &program).unwrap(),
+                    *address
+                );
+
+                let witness = qtum.decode_witness_program(address).unwrap();
+                assert_eq!(witness.version, *version);
+                assert_eq!(witness.program, program);
+                assert!(!witness.is_p2wpkh() && !witness.is_p2wsh());
+            }
+        }
+
+        assert_eq!(
+            QtumAddress::mainnet()
+                .decode_witness_program(&BECH32M_VECTORS[2].2[0].to_uppercase())
+                .unwrap()
+                .version,
+            16
+        );
+    }
+
+    #[test]
+    fn checksum_must_match_witness_version() {
+        let qtum = QtumAddress::mainnet();
+
+        // Version 1 program with a bech32 checksum
+        assert_eq!(
+            qtum.decode_witness_program(
+                "qc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqylud9p"
+            ),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        // Version 0 program with a bech32m checksum
+        assert_eq!(
+            qtum.decode_witness_program("qc1qw508d6qejxtdg4y5r3zarvary0c5xw7k4g63wd"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    /// 2-of-3 multisig witness script of the keys 1, 2 and 1 (uncompressed)
+    const WITNESS_SCRIPT: &str = "52210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f817982102c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5410479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b853ae";
+
+    #[test]
+    fn p2wsh_round_trip() {
+        let script = hex::decode(WITNESS_SCRIPT).unwrap();
+
+        for (qtum, address) in &[
+            (QtumAddress::mainnet(), MAINNET_P2WSH),
+            (QtumAddress::testnet(), TESTNET_P2WSH),
+        ] {
+            assert_eq!(qtum.p2wsh_from_script(&script).unwrap(), *address);
+
+            let witness = qtum.decode_witness_program(address).unwrap();
+            assert!(witness.is_p2wsh());
+            assert!(!witness.is_p2wpkh());
+            assert_eq!(hex::encode(witness.script_hash().unwrap()), SCRIPT_SHA256);
+        }
+
+        let p2wpkh = QtumAddress::mainnet()
+            .decode_witness_program(MAINNET_P2WPKH)
+            .unwrap();
+        assert!(p2wpkh.is_p2wpkh());
+        assert_eq!(p2wpkh.script_hash(), None);
+
+        // <key 1> OP_CHECKSIG, the BIP173 P2WSH vector with the qc prefix
+        let script =
+            hex::decode("210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac")
+                .unwrap();
+        assert_eq!(
+            QtumAddress::mainnet().p2wsh_from_script(&script).unwrap(),
+            "qc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qtd3g7a"
+        );
+
+        assert_eq!(
+            QtumAddress::mainnet().p2wsh_from_script(&[]),
+            Err(QtumAddressError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn p2wsh_is_not_truncated_to_hex() {
+        let qtum = QtumAddress::mainnet();
+
+        let p2wsh = qtum.decode_witness_program(MAINNET_P2WSH).unwrap();
+        assert_eq!(
+            p2wsh.to_hex_address(QtumNetwork::Mainnet),
+            Err(QtumAddressError::UnrepresentableWitnessProgram {
+                version: 0,
+                length: 32
+            })
+        );
+
+        let p2wpkh = qtum.decode_witness_program(MAINNET_P2WPKH).unwrap();
+        assert_eq!(
+            p2wpkh.to_hex_address(QtumNetwork::Mainnet).unwrap(),
+            PUBKEY_HASH
+        );
+    }
+}
@@ -0,0 +1,645 @@
+//! Addresses of the outputs and inputs of serialized transactions
+
+use crate::script::{is_pubkey, push_data, OP_0, OP_PUSHBYTES_20};
+use crate::{
+    hash160, Address, AddressType, QtumAddress, QtumAddressError, QtumNetwork, ScriptKind,
+    WitnessProgram,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Bytes of the previous output an input spends, txid and index
+const OUTPOINT_LEN: usize = 36;
+
+/// Bytes of an input's sequence number
+const SEQUENCE_LEN: usize = 4;
+
+/// Smallest serialized output, an 8-byte value and an empty script
+const MIN_OUTPUT_LEN: usize = 9;
+
+/// Typed address found in a transaction.
+///
+/// `Address` only covers addresses with a base58 form, native segwit addresses are kept as
+/// their witness program. `Display` renders either as shown by explorers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TxAddress {
+    /// Pubkey hash or script hash address
+    Base58(Address),
+    /// Bech32 address of a witness program on a network with a bech32 prefix
+    Segwit(QtumNetwork, WitnessProgram),
+}
+
+impl TxAddress {
+    /// The base58 address, `None` for segwit addresses
+    pub fn as_address(&self) -> Option<&Address> {
+        match self {
+            TxAddress::Base58(address) => Some(address),
+            TxAddress::Segwit(..) => None,
+        }
+    }
+
+    /// Network the address belongs to
+    pub fn network(&self) -> QtumNetwork {
+        match self {
+            TxAddress::Base58(address) => address.network(),
+            TxAddress::Segwit(network, _) => *network,
+        }
+    }
+}
+
+impl fmt::Display for TxAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TxAddress::Base58(address) => f.write_str(&address.to_base58()),
+            TxAddress::Segwit(network, witness) => f.write_str(
+                &QtumAddress::new(*network)
+                    .from_witness_program(witness.version, &witness.program)
+                    .map_err(|_| fmt::Error)?,
+            ),
+        }
+    }
+}
+
+/// Output of a transaction and the address it pays to
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TxOutAddress {
+    /// Index of the output in the transaction
+    pub vout: u32,
+    /// Output value in satoshis
+    pub value_sats: u64,
+    /// Base58 or segwit address, `None` for `OP_RETURN`, contract, coinstake marker and
+    /// other non-standard outputs, and for outputs the configured network cannot encode
+    pub address: Option<TxAddress>,
+    /// Template of the locking script, `None` when there is no address
+    pub kind: Option<ScriptKind>,
+}
+
+impl QtumAddress {
+    /// Addresses paid to by the outputs of a hex serialized transaction, in output order.
+    ///
+    /// Legacy and segwit serializations are accepted, inputs and witnesses are skipped
+    /// without being checked. Outputs without an address give `None` instead of an error, as
+    /// do witness outputs on `Custom` networks which have no bech32 prefix. Only a truncated
+    /// or malformed transaction gives `InvalidTransaction`.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// // Coinbase of Qtum mainnet block 4995, a P2PK output and the witness commitment
+    /// let tx = concat!(
+    ///     "02000000010000000000000000000000000000000000000000000000000000000000000000ffffffff",
+    ///     "050283130101ffffffff0200204aa9d1010000232103043044049b375a128ed9c97f1d4e4857b64848",
+    ///     "8da1c85ead04817be0173b06fcac0000000000000000266a24aa21a9ede2f61c3f71d1defd3fa999df",
+    ///     "a36953755c690689799962b48bebd836974e8cf900000000",
+    /// );
+    /// let outputs = QtumAddress::mainnet().tx_output_addresses(tx).unwrap();
+    ///
+    /// let address = outputs[0].address.as_ref().unwrap();
+    ///
+    /// assert_eq!(outputs[0].value_sats, 2_000_000_000_000);
+    /// assert_eq!(address.to_string(), "QbNKkvBrRwSs134WzcnnnA44dSuiUpwQd3");
+    /// assert_eq!(address.as_address().unwrap().kind(), AddressType::P2PKH);
+    /// assert_eq!(outputs[1].address, None);
+    /// ```
+    pub fn tx_output_addresses(&self, tx_hex: &str) -> Result<Vec<TxOutAddress>, QtumAddressError> {
+        if tx_hex.is_empty() {
+            return Err(QtumAddressError::EmptyInput);
+        }
+        let tx = hex::decode(tx_hex).map_err(|_| QtumAddressError::InvalidHex)?;
+        let mut reader = Reader {
+            bytes: &tx,
+            offset: 0,
+        };
+
+        reader.take(4)?;
+        // Segwit marker and flag, a legacy transaction cannot have zero inputs here
+        if reader.peek(2) == Some(&[0x00, 0x01][..]) {
+            reader.take(2)?;
+        }
+
+        let inputs = reader.compact_size()?;
+        for _ in 0..inputs {
+            reader.take(OUTPOINT_LEN)?;
+            let script_len = reader.compact_size()?;
+            reader.take(script_len)?;
+            reader.take(SEQUENCE_LEN)?;
+        }
+
+        let count = reader.compact_size()?;
+        let mut outputs = Vec::with_capacity(count.min(reader.remaining() / MIN_OUTPUT_LEN));
+        for vout in 0..count {
+            let mut value = [0u8; 8];
+            value.copy_from_slice(reader.take(8)?);
+            let script_len = reader.compact_size()?;
+            let script = reader.take(script_len)?;
+
+            let (kind, address) = match self.script_destination(script) {
+                Ok(Some((kind, address))) => (Some(kind), Some(address)),
+                Ok(None) | Err(_) => (None, None),
+            };
+            outputs.push(TxOutAddress {
+                vout: vout as u32,
+                value_sats: u64::from_le_bytes(value),
+                address,
+                kind,
+            });
+        }
+
+        Ok(outputs)
+    }
+
+    /// Address that spent an input, derived from the public key in its scriptSig or witness.
+    ///
+    /// P2PKH spends give the pubkey hash address, P2SH-P2WPKH spends the script hash address
+    /// of their redeem script and native P2WPKH spends the witness program, which only has an
+    /// address on networks with a bech32 prefix. Spends that cannot be attributed without the
+    /// previous output, such as P2PK or multisig, give `None`. A push running past the end of
+    /// the scriptSig gives `InvalidScript`.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// // Witness of the P2WPKH input of Bitcoin mainnet transaction c71d9a4f...d6e3
+    /// let signature = hex::decode(concat!(
+    ///     "304402201fa38a67a63e58b67b6cfffd02f59121ca1c8a1b22e1efe2573ae7e4b4f06c2b022002b9b4",
+    ///     "31b58f6e36b3334fb14eaecee7d2f06967a77ef50d8d5f90dda1057f0c01",
+    /// ))
+    /// .unwrap();
+    /// let pubkey = hex::decode("0257dc6ce3b1100903306f518ee8fa113d778e403f118c080b50ce079fba40e09a").unwrap();
+    /// let address = QtumAddress::mainnet().address_from_input(&[], &[&signature, &pubkey]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     address.map(|address| address.to_string()).as_deref(),
+    ///     Some("qc1qwc0rrcnzn3hprym097vgs9uavzjafs0exjwqvm")
+    /// );
+    /// ```
+    pub fn address_from_input(
+        &self,
+        script_sig: &[u8],
+        witness: &[&[u8]],
+    ) -> Result<Option<TxAddress>, QtumAddressError> {
+        let pushes = match push_data(script_sig)? {
+            Some(pushes) => pushes,
+            None => return Ok(None),
+        };
+        let base58 = |kind, hash| TxAddress::Base58(Address::new(self.network, kind, hash));
+
+        let address = match (pushes.as_slice(), witness) {
+            ([_, pubkey], []) if is_pubkey(pubkey) => base58(AddressType::P2PKH, hash160(pubkey)),
+            ([], [_, pubkey]) if is_compressed_pubkey(pubkey) => {
+                let hash = hash160(pubkey);
+                self.from_witness_program(0, &hash)?;
+                let program = WitnessProgram {
+                    version: 0,
+                    program: hash.to_vec(),
+                };
+                TxAddress::Segwit(self.network, program)
+            }
+            ([redeem_script], [_, pubkey])
+                if is_compressed_pubkey(pubkey)
+                    && redeem_script[..] == p2wpkh_program(&hash160(pubkey))[..] =>
+            {
+                base58(AddressType::P2SH, hash160(redeem_script))
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(address))
+    }
+}
+
+/// Witness spends are only standard with compressed keys
+fn is_compressed_pubkey(bytes: &[u8]) -> bool {
+    bytes.len() == 33 && is_pubkey(bytes)
+}
+
+/// `OP_0 <pubkey hash>`, the redeem script of a P2SH-P2WPKH output
+fn p2wpkh_program(hash: &[u8; 20]) -> [u8; 22] {
+    let mut script = [0u8; 22];
+    script[0] = OP_0;
+    script[1] = OP_PUSHBYTES_20;
+    script[2..].copy_from_slice(hash);
+    script
+}
+
+/// Cursor over the bytes of a transaction
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    fn peek(&self, len: usize) -> Option<&'a [u8]> {
+        self.bytes.get(self.offset..self.offset.checked_add(len)?)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], QtumAddressError> {
+        let bytes = self.peek(len).ok_or(QtumAddressError::InvalidTransaction {
+            offset: self.offset,
+        })?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    /// Bitcoin's variable length integer, counts larger than the transaction are rejected
+    fn compact_size(&mut self) -> Result<usize, QtumAddressError> {
+        let offset = self.offset;
+        let value = match self.take(1)?[0] {
+            0xfd => u64::from(u16::from_le_bytes([self.take(1)?[0], self.take(1)?[0]])),
+            0xfe => {
+                let mut bytes = [0u8; 4];
+                bytes.copy_from_slice(self.take(4)?);
+                u64::from(u32::from_le_bytes(bytes))
+            }
+            0xff => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(self.take(8)?);
+                u64::from_le_bytes(bytes)
+            }
+            value => u64::from(value),
+        };
+
+        if value > self.remaining() as u64 {
+            return Err(QtumAddressError::InvalidTransaction { offset });
+        }
+        Ok(value as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::vec;
+
+    /// Coinbase of Qtum mainnet block 4995
+    /// (0000fd3c4ed0b6dcb008b2669a3321de220d5b0716cc2984893d25111cbf5e51), txid
+    /// 54bc1f2a67b3c77c8035adce8f825dea3db3c17b8fc10b7c7a382aa1ccaa01f2: 20000 QTUM to the
+    /// key of QbNKkvBrRwSs134WzcnnnA44dSuiUpwQd3 and the `OP_RETURN` witness commitment
+    const COINBASE_TX: &str = "02000000010000000000000000000000000000000000000000000000000000000000000000ffffffff050283130101ffffffff0200204aa9d1010000232103043044049b375a128ed9c97f1d4e4857b648488da1c85ead04817be0173b06fcac0000000000000000266a24aa21a9ede2f61c3f71d1defd3fa999dfa36953755c690689799962b48bebd836974e8cf900000000";
+
+    /// Bitcoin mainnet a6eab3c14ab5272a58a5ba91505ba1a4b6d7a3a9fcbd187b6cd99a7b6d548cb7,
+    /// 1 BTC to 1KhAQw5BU3i4G8BDGpw8YqUKRLuW3mQdh. Qtum keeps Bitcoin's serialization.
+    const LEGACY_TX: &str = "0100000001a15d57094aa7a21a28cb20b59aab8fc7d1149a3bdbcddba9c622e4f5f6a99ece010000006c493046022100f93bb0e7d8db7bd46e40132d1f8242026e045f03a0efe71bbb8e3f475e970d790221009337cd7f1f929f00cc6ff01f03729b069a7c21b59b1736ddfee5db5946c5da8c0121033b9b137ee87d5a812d6f506efdd37f0affa7ffc310711c06c7f3e097c9447c52ffffffff0100e1f505000000001976a9140389035a9225b3839e2bbf32d826a1e222031fd888ac00000000";
+
+    /// Bitcoin mainnet c71d9a4fc38ee16dfc06ccc14e2192ffc4f1875d22282e3c7a9a1573a5c1d6e3, two
+    /// P2PKH inputs and a P2WPKH input paying 1410669 sats to
+    /// 19RCuR7JtUNCuxzbpoRinyEjCbCVFmmrau and 2020 sats to
+    /// bc1qwc0rrcnzn3hprym097vgs9uavzjafs0e2pdvvp
+    const SEGWIT_TX: &str = "010000000001036b6b6ac7e34e97c53c1cc74c99c7948af2e6aac75d8778004ae458d813456764000000006a473044022001deec7d9075109306320b3754188f81a8236d0d232b44bc69f8309115638b8f02204e17a5194a519cf994d0afeea1268740bdc10616b031a521113681cc415e815c012103488d3272a9fad78ee887f0684cb8ebcfc06d0945e1401d002e590c7338b163feffffffffc75bd7aa6424aee972789ec28ba181254ee6d8311b058d165bd045154d7660b0000000006b483045022100c8641bcbee3e4c47a00417875015d8c5d5ea918fb7e96f18c6ffe51bc555b401022074e2c46f5b1109cd79e39a9aa203eadd1d75356415e51d80928a5fb5feb0efee0121033504b4c6dfc3a5daaf7c425aead4c2dbbe4e7387ce8e6be2648805939ecf7054ffffffff494df3b205cd9430a26f8e8c0dc0bb80496fbc555a524d6ea307724bc7e60eee0100000000ffffffff026d861500000000001976a9145c54ed1360072ebaf56e87693b88482d2c6a101588ace407000000000000160014761e31e2629c6e11936f2f9888179d60a5d4c1f900000247304402201fa38a67a63e58b67b6cfffd02f59121ca1c8a1b22e1efe2573ae7e4b4f06c2b022002b9b431b58f6e36b3334fb14eaecee7d2f06967a77ef50d8d5f90dda1057f0c01210257dc6ce3b1100903306f518ee8fa113d778e403f118c080b50ce079fba40e09a00000000";
+
+    /// Bitcoin mainnet 0543411b2e7fbd8af4c46c593049dbac2d4007c0f5f12e4e5c71a07566e787c4, a
+    /// P2PKH input and two P2SH-P2WPKH inputs, the first output pays 3143678 sats to
+    /// 3CYBGPAe72q5wbTRYa2JE7RRzqUKafn4en
+    const NESTED_SEGWIT_TX: &str = "020000000001031cfbc8f54fbfa4a33a30068841371f80dbfe166211242213188428f437445c91000000006a47304402206fbcec8d2d2e740d824d3d36cc345b37d9f65d665a99f5bd5c9e8d42270a03a8022013959632492332200c2908459547bf8dbf97c65ab1a28dec377d6f1d41d3d63e012103d7279dfb90ce17fe139ba60a7c41ddf605b25e1c07a4ddcb9dfef4e7d6710f48feffffff476222484f5e35b3f0e43f65fc76e21d8be7818dd6a989c160b1e5039b7835fc00000000171600140914414d3c94af70ac7e25407b0689e0baa10c77feffffffa83d954a62568bbc99cc644c62eb7383d7c2a2563041a0aeb891a6a4055895570000000017160014795d04cc2d4f31480d9a3710993fbd80d04301dffeffffff06fef72f000000000017a91476fd7035cd26f1a32a5ab979e056713aac25796887a5000f00000000001976a914b8332d502a529571c6af4be66399cd33379071c588ac3fda0500000000001976a914fc1d692f8de10ae33295f090bea5fe49527d975c88ac522e1b00000000001976a914808406b54d1044c429ac54c0e189b0d8061667e088ac6eb68501000000001976a914dfab6085f3a8fb3e6710206a5a959313c5618f4d88acbba20000000000001976a914eb3026552d7e3f3073457d0bee5d4757de48160d88ac0002483045022100bee24b63212939d33d513e767bc79300051f7a0d433c3fcf1e0e3bf03b9eb1d70220588dc45a9ce3a939103b4459ce47500b64e23ab118dfc03c9caa7d6bfc32b9c601210354fd80328da0f9ae6eef2b3a81f74f9a6f66761fadf96f1d1d22b1fd6845876402483045022100e29c7e3a5efc10da6269e5fc20b6a1cb8beb92130cc52c67e46ef40aaa5cac5f0220644dd1b049727d991aece98a105563416e10a5ac4221abac7d16931842d5c322012103960b87412d6e169f30e12106bdf70122aabb9eb61f455518322a18b920a4dfa887d30700";
+
+    /// Built by hand in the coinstake layout, an empty first output before the stake
+    const COINSTAKE_TX: &str = "0200000001f452c03045466504f026c35f1be8e63d8e977f0db89bdabc513a15ba498fe14c0000000000ffffffff020000000000000000000084d717000000001976a9146c89a1a6ca2ae7c00b248bb2832d6f480f27da6888ac00000000";
+
+    /// Bitcoin's pubkey hash prefix, to compare with the addresses shown by its explorers
+    const BITCOIN: QtumNetwork = QtumNetwork::Custom(0x00);
+
+    /// Rendered address of an output
+    fn rendered(output: &TxOutAddress) -> Option<String> {
+        output.address.as_ref().map(ToString::to_string)
+    }
+
+    fn hash20(hex: &str) -> [u8; 20] {
+        let mut hash = [0u8; 20];
+        hash.copy_from_slice(&hex::decode(hex).unwrap());
+        hash
+    }
+
+    #[test]
+    fn coinbase_outputs() {
+        let outputs = QtumAddress::mainnet()
+            .tx_output_addresses(COINBASE_TX)
+            .unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].vout, 0);
+        assert_eq!(outputs[0].value_sats, 2_000_000_000_000);
+        assert_eq!(
+            rendered(&outputs[0]).as_deref(),
+            Some("QbNKkvBrRwSs134WzcnnnA44dSuiUpwQd3")
+        );
+        assert_eq!(outputs[0].kind, Some(ScriptKind::P2PK));
+
+        assert_eq!(outputs[1].vout, 1);
+        assert_eq!(outputs[1].value_sats, 0);
+        assert_eq!(outputs[1].address, None);
+        assert_eq!(outputs[1].kind, None);
+    }
+
+    #[test]
+    fn legacy_outputs() {
+        let outputs = QtumAddress::new(BITCOIN)
+            .tx_output_addresses(LEGACY_TX)
+            .unwrap();
+
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].value_sats, 100_000_000);
+        assert_eq!(
+            rendered(&outputs[0]).as_deref(),
+            Some("1KhAQw5BU3i4G8BDGpw8YqUKRLuW3mQdh")
+        );
+        assert_eq!(outputs[0].kind, Some(ScriptKind::P2PKH));
+
+        let outputs = QtumAddress::mainnet()
+            .tx_output_addresses(LEGACY_TX)
+            .unwrap();
+        assert_eq!(
+            outputs[0].address,
+            Some(TxAddress::Base58(Address::new(
+                QtumNetwork::Mainnet,
+                AddressType::P2PKH,
+                hash20("0389035a9225b3839e2bbf32d826a1e222031fd8")
+            )))
+        );
+        assert_eq!(
+            rendered(&outputs[0]).as_deref(),
+            Some("QLvgGiDnMvvXVQDCdc9RFpd6pgHcojCvXc")
+        );
+    }
+
+    #[test]
+    fn segwit_outputs() {
+        let outputs = QtumAddress::mainnet()
+            .tx_output_addresses(SEGWIT_TX)
+            .unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].value_sats, 1_410_669);
+        assert_eq!(
+            rendered(&outputs[0]).as_deref(),
+            Some("QV2C1iQ24wF2M75dF8kCvF2Mhr9CWJsz3i")
+        );
+        assert_eq!(outputs[0].kind, Some(ScriptKind::P2PKH));
+
+        assert_eq!(outputs[1].value_sats, 2020);
+        assert_eq!(
+            outputs[1].address,
+            Some(TxAddress::Segwit(
+                QtumNetwork::Mainnet,
+                WitnessProgram {
+                    version: 0,
+                    program: hex::decode("761e31e2629c6e11936f2f9888179d60a5d4c1f9").unwrap(),
+                }
+            ))
+        );
+        assert_eq!(
+            rendered(&outputs[1]).as_deref(),
+            Some("qc1qwc0rrcnzn3hprym097vgs9uavzjafs0exjwqvm")
+        );
+        assert_eq!(outputs[1].kind, Some(ScriptKind::P2WPKH));
+    }
+
+    #[test]
+    fn script_hash_outputs() {
+        let outputs = QtumAddress::mainnet()
+            .tx_output_addresses(NESTED_SEGWIT_TX)
+            .unwrap();
+
+        assert_eq!(outputs.len(), 6);
+        assert_eq!(outputs[0].value_sats, 3_143_678);
+        assert_eq!(
+            outputs[0].address,
+            Some(TxAddress::Base58(Address::new(
+                QtumNetwork::Mainnet,
+                AddressType::P2SH,
+                hash20("76fd7035cd26f1a32a5ab979e056713aac257968")
+            )))
+        );
+        assert_eq!(
+            rendered(&outputs[0]).as_deref(),
+            Some("MJkKaGac49gWk6jKeT1e3kfqKY4mVXpZwD")
+        );
+        assert_eq!(outputs[0].kind, Some(ScriptKind::P2SH));
+        assert!(outputs[1..]
+            .iter()
+            .all(|output| output.kind == Some(ScriptKind::P2PKH)));
+    }
+
+    #[test]
+    fn outputs_without_address_on_custom_networks() {
+        // Custom networks have no bech32 prefix: the witness output has no address, the
+        // pubkey hash output still has one
+        let outputs = QtumAddress::new(BITCOIN)
+            .tx_output_addresses(SEGWIT_TX)
+            .unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(
+            rendered(&outputs[0]).as_deref(),
+            Some("19RCuR7JtUNCuxzbpoRinyEjCbCVFmmrau")
+        );
+        assert_eq!(
+            outputs[0].address.as_ref().map(TxAddress::network),
+            Some(BITCOIN)
+        );
+        assert_eq!(outputs[1].address, None);
+        assert_eq!(outputs[1].kind, None);
+    }
+
+    #[test]
+    fn coinstake_marker_output() {
+        let outputs = QtumAddress::testnet()
+            .tx_output_addresses(COINSTAKE_TX)
+            .unwrap();
+
+        assert_eq!(outputs[0].value_sats, 0);
+        assert_eq!(outputs[0].address, None);
+        assert_eq!(outputs[1].value_sats, 400_000_000);
+        assert_eq!(outputs[1].kind, Some(ScriptKind::P2PKH));
+    }
+
+    /// scriptSig of the P2PKH input of `NESTED_SEGWIT_TX`, it spends
+    /// 915c4437f4288418132224116216fedb801f37418806303aa3a4bf4ff5c8fb1c:0 which pays
+    /// 1FX8j7QCk3SVNwHMy1C23unZ2P91yeDwb4
+    const P2PKH_SCRIPT_SIG: &str = "47304402206fbcec8d2d2e740d824d3d36cc345b37d9f65d665a99f5bd5c9e8d42270a03a8022013959632492332200c2908459547bf8dbf97c65ab1a28dec377d6f1d41d3d63e012103d7279dfb90ce17fe139ba60a7c41ddf605b25e1c07a4ddcb9dfef4e7d6710f48";
+
+    /// scriptSig and witness of the second input of `NESTED_SEGWIT_TX`, it spends
+    /// fc35789b03e5b160c189a9d68d81e78b1de276fc653fe4f0b3355e4f48226247:0 which pays
+    /// 3PtiJnVm13MhC71S9bVomQ65jmqC2PoEvY
+    const P2SH_P2WPKH_SCRIPT_SIG: &str = "1600140914414d3c94af70ac7e25407b0689e0baa10c77";
+    const P2SH_P2WPKH_WITNESS: [&str; 2] = [
+        "3045022100bee24b63212939d33d513e767bc79300051f7a0d433c3fcf1e0e3bf03b9eb1d70220588dc45a9ce3a939103b4459ce47500b64e23ab118dfc03c9caa7d6bfc32b9c601",
+        "0354fd80328da0f9ae6eef2b3a81f74f9a6f66761fadf96f1d1d22b1fd68458764",
+    ];
+
+    /// Witness of the P2WPKH input of `SEGWIT_TX`, its key also receives the second output
+    /// bc1qwc0rrcnzn3hprym097vgs9uavzjafs0e2pdvvp
+    const P2WPKH_WITNESS: [&str; 2] = [
+        "304402201fa38a67a63e58b67b6cfffd02f59121ca1c8a1b22e1efe2573ae7e4b4f06c2b022002b9b431b58f6e36b3334fb14eaecee7d2f06967a77ef50d8d5f90dda1057f0c01",
+        "0257dc6ce3b1100903306f518ee8fa113d778e403f118c080b50ce079fba40e09a",
+    ];
+
+    fn witness(items: &[&str; 2]) -> [Vec<u8>; 2] {
+        [
+            hex::decode(items[0]).unwrap(),
+            hex::decode(items[1]).unwrap(),
+        ]
+    }
+
+    fn push(data: &[u8]) -> Vec<u8> {
+        let mut script = vec![data.len() as u8];
+        script.extend_from_slice(data);
+        script
+    }
+
+    #[test]
+    fn input_addresses() {
+        let script_sig = hex::decode(P2PKH_SCRIPT_SIG).unwrap();
+        let [nested_signature, nested_pubkey] = witness(&P2SH_P2WPKH_WITNESS);
+        let nested_script_sig = hex::decode(P2SH_P2WPKH_SCRIPT_SIG).unwrap();
+        let [signature, pubkey] = witness(&P2WPKH_WITNESS);
+
+        // Senders match the Bitcoin addresses of the spent outputs
+        let bitcoin = QtumAddress::new(BITCOIN);
+        assert_eq!(
+            bitcoin
+                .address_from_input(&script_sig, &[])
+                .unwrap()
+                .map(|address| address.to_string())
+                .as_deref(),
+            Some("1FX8j7QCk3SVNwHMy1C23unZ2P91yeDwb4")
+        );
+        assert_eq!(
+            bitcoin
+                .address_from_input(&nested_script_sig, &[&nested_signature, &nested_pubkey])
+                .unwrap(),
+            Some(TxAddress::Base58(Address::new(
+                BITCOIN,
+                AddressType::P2SH,
+                hash20("f3890da1b99e44cd3d52f7bcea6a1351658ea7be")
+            )))
+        );
+
+        // The P2WPKH sender is the address of the change output
+        let change = QtumAddress::mainnet()
+            .tx_output_addresses(SEGWIT_TX)
+            .unwrap()
+            .remove(1)
+            .address;
+        assert!(change.is_some());
+
+        // the sender's kind comes from the spend, not from the converter's address type
+        for qtum in &[
+            QtumAddress::mainnet(),
+            QtumAddress::new_p2sh(QtumNetwork::Mainnet),
+        ] {
+            let sender = |script_sig: &[u8], witness: &[&[u8]]| {
+                qtum.address_from_input(script_sig, witness)
+                    .unwrap()
+                    .map(|address| address.to_string())
+            };
+
+            assert_eq!(
+                sender(&script_sig, &[]).as_deref(),
+                Some("Qb87qQguvWKJp5NPPLWWBBaBXe5jCVNzjw")
+            );
+            assert_eq!(
+                sender(&nested_script_sig, &[&nested_signature, &nested_pubkey]).as_deref(),
+                Some("MW6rcfuixAD7zcHLFUV9b3LV4URe6eQtHy")
+            );
+            assert_eq!(
+                qtum.address_from_input(&[], &[&signature, &pubkey])
+                    .unwrap(),
+                change
+            );
+        }
+
+        // Without a bech32 prefix a native witness spend has no address
+        assert_eq!(
+            bitcoin.address_from_input(&[], &[&signature, &pubkey]),
+            Err(QtumAddressError::UnknownNetwork { prefix: 0x00 })
+        );
+    }
+
+    #[test]
+    fn unattributable_inputs() {
+        let qtum = QtumAddress::mainnet();
+        let [signature, pubkey] = witness(&P2WPKH_WITNESS);
+
+        // P2PK and bare multisig spends only carry signatures
+        for script_sig in &[
+            push(&signature),
+            [vec![0x00], push(&signature), push(&signature)].concat(),
+        ] {
+            assert_eq!(qtum.address_from_input(script_sig, &[]), Ok(None));
+        }
+
+        // Redeem script of another key
+        let other = hex::decode(P2SH_P2WPKH_SCRIPT_SIG).unwrap();
+        assert_eq!(
+            qtum.address_from_input(&other, &[&signature, &pubkey]),
+            Ok(None)
+        );
+
+        // Non-push opcodes and uncompressed witness keys
+        let script_sig = [push(&signature), push(&pubkey), vec![0xac]].concat();
+        assert_eq!(qtum.address_from_input(&script_sig, &[]), Ok(None));
+        let uncompressed = [&[0x04][..], &[0x11; 64][..]].concat();
+        assert_eq!(
+            qtum.address_from_input(&[], &[&signature, &uncompressed]),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn malformed_pushes() {
+        let qtum = QtumAddress::mainnet();
+
+        for (script_sig, offset) in &[
+            (&[0x47, 0x30, 0x44][..], 0),
+            (&[0x01, 0x01, 0x4c][..], 2),
+            (&[0x4c, 0x02, 0x01][..], 0),
+            (&[0x4d, 0x01][..], 0),
+            (&[0x4e, 0xff, 0xff, 0xff, 0xff, 0x00][..], 0),
+        ] {
+            assert_eq!(
+                qtum.address_from_input(script_sig, &[]),
+                Err(QtumAddressError::InvalidScript { offset: *offset })
+            );
+        }
+
+        // Every push opcode reads its length
+        assert_eq!(
+            qtum.address_from_input(&[0x4c, 0x00, 0x4d, 0x00, 0x00, 0x4e, 0, 0, 0, 0], &[]),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn malformed_transactions() {
+        let qtum = QtumAddress::testnet();
+
+        assert_eq!(
+            qtum.tx_output_addresses(""),
+            Err(QtumAddressError::EmptyInput)
+        );
+        assert_eq!(
+            qtum.tx_output_addresses("0200000001zz"),
+            Err(QtumAddressError::InvalidHex)
+        );
+
+        // Every truncation fails instead of panicking
+        for len in (0..LEGACY_TX.len() - 8).step_by(2).skip(1) {
+            assert!(matches!(
+                qtum.tx_output_addresses(&LEGACY_TX[..len]),
+                Err(QtumAddressError::InvalidTransaction { .. })
+            ));
+        }
+
+        // An output count far beyond the transaction's size
+        assert_eq!(
+            qtum.tx_output_addresses("0100000000ffffffffffffffffff"),
+            Err(QtumAddressError::InvalidTransaction { offset: 5 })
+        );
+    }
+}
@@ -0,0 +1,739 @@
+use crate::{decode_address_bytes, decode_hex, QtumAddressError, QtumNetwork, ADDRESS_PAYLOAD_LEN};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::FromStr;
+
+/// Hex address for use in smart contracts.
+///
+/// Holds the raw 20 bytes together with their rendering, which is lowercase and without
+/// the `0x` prefix unless the converter was configured with another `HexFormat`.
+/// Parsing always normalizes to that default form. Equality, ordering and hashing only look
+/// at the bytes, so differently formatted renderings of one address are equal. Comparing
+/// with a `str` instead checks the rendering exactly, which is what tests of a `HexFormat`
+/// need; parse the string first to compare addresses.
+#[derive(Debug, Clone)]
+pub struct HexAddress {
+    bytes: [u8; ADDRESS_PAYLOAD_LEN],
+    text: String,
+}
+
+/// Rendering of hex addresses: letter case and the `0x` prefix
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let format = HexFormat::new().uppercase(true).prefix(true);
+/// let qtum = QtumAddress::new(QtumNetwork::Testnet).with_hex_format(format);
+///
+/// let hex = qtum.gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+/// assert_eq!(hex, "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HexFormat {
+    uppercase: bool,
+    prefix: bool,
+}
+
+/// Base58 pubkeyhash address, ordered by its string
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Base58Address(pub(crate) String);
+
+impl HexAddress {
+    /// Getting the address as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Raw 20 bytes of the address
+    pub fn to_bytes(&self) -> [u8; ADDRESS_PAYLOAD_LEN] {
+        self.bytes
+    }
+
+    /// Shortened form for display, e.g. `0x6c89a1…27da68`, also available as `{:#}`
+    ///
+    /// A `0x` prefix stays attached to the leading group and is not counted.
+    pub fn short(&self) -> String {
+        self.short_with(SHORT_DEFAULT_LEN, SHORT_DEFAULT_LEN)
+    }
+
+    /// Shortened form keeping the first `head` and last `tail` hex characters
+    pub fn short_with(&self, head: usize, tail: usize) -> String {
+        shorten(&self.text, head, tail)
+    }
+}
+
+/// Default number of characters kept on each side by `short`
+pub const SHORT_DEFAULT_LEN: usize = 6;
+
+/// Keeping the first `head` and last `tail` characters of an address around an ellipsis,
+/// a `0x` prefix is kept in addition to the leading group. Inputs that would not get
+/// shorter are returned unchanged.
+pub(crate) fn shorten(address: &str, head: usize, tail: usize) -> String {
+    let (prefix, rest) = match address.get(..2) {
+        Some("0x") | Some("0X") => address.split_at(2),
+        _ => ("", address),
+    };
+
+    let chars: Vec<char> = rest.chars().collect();
+    if chars.len() <= head.saturating_add(tail).saturating_add(1) {
+        return address.to_string();
+    }
+
+    let mut short = String::with_capacity(prefix.len() + head + tail + 3);
+    short.push_str(prefix);
+    short.extend(&chars[..head]);
+    short.push('\u{2026}');
+    short.extend(&chars[chars.len() - tail..]);
+
+    short
+}
+
+impl HexFormat {
+    /// Bare lowercase, the format used by default
+    pub const fn new() -> Self {
+        Self {
+            uppercase: false,
+            prefix: false,
+        }
+    }
+
+    /// Rendering hex digits in uppercase
+    pub const fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Emitting the `0x` prefix
+    pub const fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Rendering raw address bytes in this format
+    pub fn format(&self, bytes: &[u8; ADDRESS_PAYLOAD_LEN]) -> HexAddress {
+        let hex = if self.uppercase {
+            hex::encode_upper(bytes)
+        } else {
+            hex::encode(bytes)
+        };
+
+        HexAddress {
+            bytes: *bytes,
+            text: if self.prefix {
+                format!("0x{}", hex)
+            } else {
+                hex
+            },
+        }
+    }
+}
+
+impl Base58Address {
+    /// Getting the address as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Shortened form for display, e.g. `qTTH1Y…8pyrBt`, also available as `{:#}`
+    pub fn short(&self) -> String {
+        self.short_with(SHORT_DEFAULT_LEN, SHORT_DEFAULT_LEN)
+    }
+
+    /// Shortened form keeping the first `head` and last `tail` characters
+    pub fn short_with(&self, head: usize, tail: usize) -> String {
+        shorten(&self.0, head, tail)
+    }
+}
+
+impl From<[u8; ADDRESS_PAYLOAD_LEN]> for HexAddress {
+    fn from(bytes: [u8; ADDRESS_PAYLOAD_LEN]) -> Self {
+        HexFormat::new().format(&bytes)
+    }
+}
+
+impl FromStr for HexAddress {
+    type Err = QtumAddressError;
+
+    /// Parsing a 40 character hex address in any case with an optional `0x` prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode_hex(s)?;
+        Ok(HexAddress::from(bytes))
+    }
+}
+
+impl FromStr for Base58Address {
+    type Err = QtumAddressError;
+
+    /// Parsing a base58check address, the checksum is verified but the network is not
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_address_bytes(s, true)?;
+        Ok(Base58Address(s.to_string()))
+    }
+}
+
+impl TryFrom<&str> for HexAddress {
+    type Error = QtumAddressError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<&str> for Base58Address {
+    type Error = QtumAddressError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Parsing a base58check address of the given network, pubkey hash or script hash
+///
+/// ```rust
+/// use qtum_address_rust::*;
+/// use std::convert::TryFrom;
+///
+/// let addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+/// assert!(Base58Address::try_from((addr, QtumNetwork::Testnet)).is_ok());
+/// assert!(Base58Address::try_from((addr, QtumNetwork::Mainnet)).is_err());
+/// ```
+impl TryFrom<(&str, QtumNetwork)> for Base58Address {
+    type Error = QtumAddressError;
+
+    fn try_from((value, network): (&str, QtumNetwork)) -> Result<Self, Self::Error> {
+        let version = decode_address_bytes(value, true)?[0];
+
+        if version != network.to_prefix_byte() && version != network.to_script_prefix_byte() {
+            return Err(QtumAddressError::NetworkMismatch {
+                expected: network.to_prefix_byte(),
+                found: version,
+            });
+        }
+
+        Ok(Base58Address(value.to_string()))
+    }
+}
+
+/// Comparing the address bytes, which ignores letter case and the `0x` prefix
+impl PartialEq for HexAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for HexAddress {}
+
+/// Ordering by the 20 address bytes
+impl Ord for HexAddress {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl PartialOrd for HexAddress {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Hashing the address bytes, which ignores letter case and the `0x` prefix
+impl Hash for HexAddress {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+/// Sorting hex addresses by their bytes, e.g. for address indexes
+pub fn sort_addresses(addresses: &mut [HexAddress]) {
+    addresses.sort();
+}
+
+impl fmt::Display for HexAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.short())
+        } else {
+            f.write_str(&self.text)
+        }
+    }
+}
+
+impl fmt::Display for Base58Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.short())
+        } else {
+            f.write_str(&self.0)
+        }
+    }
+}
+
+impl AsRef<str> for HexAddress {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+/// The raw 20 bytes, e.g. for contract calldata
+impl AsRef<[u8]> for HexAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl AsRef<str> for Base58Address {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for HexAddress {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Deref for Base58Address {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<HexAddress> for String {
+    fn from(address: HexAddress) -> Self {
+        address.text
+    }
+}
+
+impl From<HexAddress> for [u8; ADDRESS_PAYLOAD_LEN] {
+    fn from(address: HexAddress) -> Self {
+        address.bytes
+    }
+}
+
+impl From<Base58Address> for String {
+    fn from(address: Base58Address) -> Self {
+        address.0
+    }
+}
+
+/// Comparing the rendering, letter case and `0x` prefix included
+impl PartialEq<str> for HexAddress {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for HexAddress {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+impl PartialEq<HexAddress> for &str {
+    fn eq(&self, other: &HexAddress) -> bool {
+        *self == other.text
+    }
+}
+
+impl PartialEq<str> for Base58Address {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Base58Address {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Base58Address> for &str {
+    fn eq(&self, other: &Base58Address) -> bool {
+        *self == other.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for HexAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HexAddress {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Base58Address {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Base58Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{QtumAddress, QtumNetwork};
+    use alloc::vec;
+
+    #[test]
+    fn hex_address_order_follows_bytes() {
+        let low: HexAddress = "00ffffffffffffffffffffffffffffffffffffff".parse().unwrap();
+        let mid: HexAddress = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68".parse().unwrap();
+        let high: HexAddress = "ff00000000000000000000000000000000000000".parse().unwrap();
+        let upper = HexFormat::new()
+            .uppercase(true)
+            .prefix(true)
+            .format(&mid.to_bytes());
+
+        assert!(low < mid && mid < high && low < high);
+        assert!(upper > low && upper < high);
+        assert_eq!(upper, mid);
+        assert_eq!(upper.cmp(&mid), Ordering::Equal);
+        assert_eq!(mid.cmp(&mid.clone()), Ordering::Equal);
+
+        let all = [&low, &mid, &high, &upper];
+        for a in &all {
+            for b in &all {
+                assert_eq!(a == b, a.cmp(b) == Ordering::Equal);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse());
+                for c in &all {
+                    if a <= b && b <= c {
+                        assert!(a <= c);
+                    }
+                }
+            }
+        }
+
+        let mut addresses = vec![high.clone(), upper, mid.clone(), low.clone()];
+        sort_addresses(&mut addresses);
+        assert_eq!(addresses[0], low);
+        assert_eq!(addresses[1].to_bytes(), mid.to_bytes());
+        assert_eq!(addresses[2].to_bytes(), mid.to_bytes());
+        assert_eq!(addresses[3], high);
+
+        let mut sorted = addresses.clone();
+        sorted.sort();
+        assert_eq!(sorted, addresses);
+    }
+
+    #[test]
+    fn str_comparison_checks_rendering() {
+        let lower: HexAddress = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68".parse().unwrap();
+        let upper = HexFormat::new()
+            .uppercase(true)
+            .prefix(true)
+            .format(&lower.to_bytes());
+
+        assert_eq!(lower, upper);
+        assert_eq!(upper, "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68");
+        assert_ne!(lower, "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68");
+        assert_ne!("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68", upper);
+
+        // Parsing first compares the bytes
+        assert_eq!(
+            lower,
+            "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68"
+                .parse::<HexAddress>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn addresses_as_hash_keys() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::{HashMap, HashSet};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let lower: HexAddress = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68".parse().unwrap();
+        let upper = HexFormat::new()
+            .uppercase(true)
+            .prefix(true)
+            .format(&lower.to_bytes());
+        assert_eq!(hash_of(&lower), hash_of(&lower.clone()));
+        assert_eq!(hash_of(&lower), hash_of(&upper));
+
+        let mut balances = HashMap::new();
+        balances.insert(lower.clone(), 10u64);
+        *balances.entry(upper.clone()).or_insert(0) += 5;
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[&lower], 15);
+        assert_eq!(balances[&upper], 15);
+
+        let addr: Base58Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+        let other: Base58Address = "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG".parse().unwrap();
+        let set: HashSet<_> = vec![addr.clone(), other, addr.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&addr));
+    }
+
+    #[test]
+    fn base58_address_order_follows_string() {
+        let a: Base58Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+        let b: Base58Address = "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG".parse().unwrap();
+
+        assert!(b < a);
+        assert_eq!(a.cmp(&b), a.as_str().cmp(b.as_str()));
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn hex_address_from_bytes() {
+        let bytes = [
+            0x6c, 0x89, 0xa1, 0xa6, 0xca, 0x2a, 0xe7, 0xc0, 0x0b, 0x24, 0x8b, 0xb2, 0x83, 0x2d,
+            0x6f, 0x48, 0x0f, 0x27, 0xda, 0x68,
+        ];
+
+        let hex = HexAddress::from(bytes);
+        assert_eq!(hex, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+    }
+
+    #[test]
+    fn hex_address_bytes() {
+        let bytes = [
+            0x6c, 0x89, 0xa1, 0xa6, 0xca, 0x2a, 0xe7, 0xc0, 0x0b, 0x24, 0x8b, 0xb2, 0x83, 0x2d,
+            0x6f, 0x48, 0x0f, 0x27, 0xda, 0x68,
+        ];
+
+        let hex: HexAddress = "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68"
+            .parse()
+            .unwrap();
+        assert_eq!(AsRef::<[u8]>::as_ref(&hex), &bytes[..]);
+        assert_eq!(hex.to_string(), "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+        assert_eq!(<[u8; 20]>::from(hex.clone()), bytes);
+        assert_eq!(HexAddress::from(<[u8; 20]>::from(hex.clone())), hex);
+
+        let formatted = HexFormat::new().uppercase(true).format(&bytes);
+        assert_eq!(AsRef::<[u8]>::as_ref(&formatted), &bytes[..]);
+        assert_eq!(
+            formatted.as_str(),
+            "6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68"
+        );
+    }
+
+    #[test]
+    fn hex_address_from_str() {
+        let hex: HexAddress = "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68"
+            .parse()
+            .unwrap();
+        assert_eq!(hex.as_str(), "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+
+        assert_eq!(
+            HexAddress::try_from("6c89a1a6"),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 8
+            })
+        );
+        assert_eq!(
+            HexAddress::try_from("6c89a1a6ca2ae7c00b248bb2832d6f480f27dazz"),
+            Err(QtumAddressError::InvalidHex)
+        );
+        assert_eq!(
+            HexAddress::try_from("6C89a1a6Ca2ae7c00b248bb2832d6f480f27dA68").unwrap(),
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        );
+    }
+
+    #[test]
+    fn hex_format_combinations() {
+        let bytes = [
+            0x6c, 0x89, 0xa1, 0xa6, 0xca, 0x2a, 0xe7, 0xc0, 0x0b, 0x24, 0x8b, 0xb2, 0x83, 0x2d,
+            0x6f, 0x48, 0x0f, 0x27, 0xda, 0x68,
+        ];
+
+        let cases = [
+            (false, false, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"),
+            (false, true, "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"),
+            (true, false, "6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68"),
+            (true, true, "0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68"),
+        ];
+
+        for (uppercase, prefix, expected) in cases.iter() {
+            let format = HexFormat::new().uppercase(*uppercase).prefix(*prefix);
+            assert_eq!(format.format(&bytes), *expected);
+
+            let qtum = QtumAddress::new(QtumNetwork::Testnet).with_hex_format(format);
+            let hex = qtum
+                .gethexaddress("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt")
+                .unwrap();
+            assert_eq!(hex, *expected);
+            assert_eq!(
+                qtum.fromhexaddress(&hex).unwrap(),
+                "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+            );
+
+            let addr = crate::Address::from_hex(expected, QtumNetwork::Testnet).unwrap();
+            assert_eq!(addr.to_hex_with(format), *expected);
+        }
+
+        assert_eq!(HexFormat::default(), HexFormat::new());
+    }
+
+    #[test]
+    fn short_display() {
+        let base58: Base58Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+        assert_eq!(base58.short(), "qTTH1Y\u{2026}8pyrBt");
+        assert_eq!(format!("{:#}", base58), "qTTH1Y\u{2026}8pyrBt");
+        assert_eq!(base58.short_with(4, 2), "qTTH\u{2026}Bt");
+        assert_eq!(base58.short_with(20, 20), base58.as_str());
+
+        let hex = QtumAddress::new(QtumNetwork::Testnet)
+            .with_hex_format(HexFormat::new().prefix(true))
+            .gethexaddress(&base58)
+            .unwrap();
+        assert_eq!(hex.short(), "0x6c89a1\u{2026}27da68");
+        assert_eq!(format!("{:#}", hex), "0x6c89a1\u{2026}27da68");
+
+        let bare = HexAddress::from(decode_hex(&hex).unwrap());
+        assert_eq!(bare.short(), "6c89a1\u{2026}27da68");
+    }
+
+    #[test]
+    fn shorten_never_panics() {
+        assert_eq!(shorten("", 6, 6), "");
+        assert_eq!(shorten("0x", 6, 6), "0x");
+        assert_eq!(shorten("0", 6, 6), "0");
+        assert_eq!(shorten("qTTH1Yr2eKCuD", 6, 6), "qTTH1Yr2eKCuD");
+        assert_eq!(shorten("qTTH1Yr2eKCuDL", 6, 6), "qTTH1Y\u{2026}eKCuDL");
+        assert_eq!(
+            shorten("\u{430}\u{431}\u{432}\u{433}", 1, 1),
+            "\u{430}\u{2026}\u{433}"
+        );
+        assert_eq!(shorten("abc", usize::MAX, usize::MAX), "abc");
+        assert_eq!(shorten("abcdef", 0, 0), "\u{2026}");
+    }
+
+    #[test]
+    fn base58_address_from_str() {
+        let addr = Base58Address::try_from("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt").unwrap();
+        assert_eq!(addr.to_string(), "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt");
+
+        assert_eq!(
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu".parse::<Base58Address>(),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn base58_address_for_network() {
+        let addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+        let p2sh = QtumAddress::new_p2sh(QtumNetwork::Testnet)
+            .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap();
+
+        for network in &[QtumNetwork::Testnet, QtumNetwork::Regtest] {
+            assert_eq!(Base58Address::try_from((addr, *network)).unwrap(), addr);
+            assert_eq!(
+                Base58Address::try_from((p2sh.as_str(), *network)).unwrap(),
+                p2sh
+            );
+        }
+
+        assert_eq!(
+            Base58Address::try_from((addr, QtumNetwork::Mainnet)),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+        assert_eq!(
+            Base58Address::try_from(("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu", QtumNetwork::Testnet)),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn newtypes_round_trip_through_converter() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        let base58: Base58Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+        let hex = qtum.gethexaddress(&base58).unwrap();
+        assert_eq!(qtum.fromhexaddress(&hex).unwrap(), base58);
+
+        let as_str: &str = hex.as_ref();
+        assert_eq!(as_str.len(), 40);
+        assert_eq!(String::from(hex).len(), 40);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+    use crate::QtumNetwork;
+
+    #[test]
+    fn network_as_string() {
+        assert_eq!(
+            serde_json::to_string(&QtumNetwork::Mainnet).unwrap(),
+            "\"mainnet\""
+        );
+        assert_eq!(
+            serde_json::from_str::<QtumNetwork>("\"testnet\"").unwrap(),
+            QtumNetwork::Testnet
+        );
+
+        let custom = serde_json::to_string(&QtumNetwork::Custom(0x1f)).unwrap();
+        assert_eq!(custom, "{\"custom\":31}");
+        assert_eq!(
+            serde_json::from_str::<QtumNetwork>(&custom).unwrap(),
+            QtumNetwork::Custom(0x1f)
+        );
+    }
+
+    #[test]
+    fn addresses_round_trip() {
+        let hex: HexAddress = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68".parse().unwrap();
+        let json = serde_json::to_string(&hex).unwrap();
+        assert_eq!(json, "\"6c89a1a6ca2ae7c00b248bb2832d6f480f27da68\"");
+        assert_eq!(serde_json::from_str::<HexAddress>(&json).unwrap(), hex);
+
+        let base58: Base58Address = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".parse().unwrap();
+        let json = serde_json::to_string(&base58).unwrap();
+        assert_eq!(json, "\"qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt\"");
+        assert_eq!(
+            serde_json::from_str::<Base58Address>(&json).unwrap(),
+            base58
+        );
+    }
+
+    #[test]
+    fn invalid_addresses_are_rejected() {
+        assert!(serde_json::from_str::<HexAddress>("\"0xhello\"").is_err());
+        assert!(
+            serde_json::from_str::<Base58Address>("\"qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu\"")
+                .is_err()
+        );
+    }
+}
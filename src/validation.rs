@@ -0,0 +1,271 @@
+use crate::{
+    decode_address_bytes, decode_hex, HexAddress, QtumAddress, QtumAddressError, QtumNetwork,
+};
+use alloc::vec::Vec;
+
+/// Summary of a bulk validation run
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct ValidationReport {
+    /// Result of every input, in input order
+    pub results: Vec<Result<(), QtumAddressError>>,
+    /// Number of valid addresses
+    pub valid: usize,
+    /// Number of addresses with a wrong checksum
+    pub bad_checksum: usize,
+    /// Number of addresses belonging to another network
+    pub wrong_network: usize,
+    /// Number of addresses of the configured network but of another kind, such as script hash
+    /// addresses given to a pubkey hash converter
+    pub wrong_type: usize,
+    /// Number of inputs that are not addresses at all
+    pub malformed: usize,
+    /// Indices of all invalid inputs
+    pub invalid_indices: Vec<usize>,
+}
+
+impl ValidationReport {
+    /// Total number of validated inputs
+    pub fn total(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether every input was a valid address
+    pub fn all_valid(&self) -> bool {
+        self.invalid_indices.is_empty()
+    }
+}
+
+impl QtumAddress {
+    /// Checks prefix, encoding and checksum of a base58 address without converting it
+    pub fn validate_address(&self, address: &str) -> Result<(), QtumAddressError> {
+        self.decode(address, true).map(|_| ())
+    }
+
+    /// Validates many base58 addresses at once, without converting them.
+    ///
+    /// ```rust
+    /// use qtum_address_rust::*;
+    ///
+    /// let qtum = QtumAddress::new(QtumNetwork::Testnet);
+    /// let report = qtum.validate_many(vec!["qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt", "garbage"]);
+    ///
+    /// assert_eq!(report.valid, 1);
+    /// assert_eq!(report.malformed, 1);
+    /// assert_eq!(report.invalid_indices, vec![1]);
+    /// ```
+    pub fn validate_many<I>(&self, addresses: I) -> ValidationReport
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut report = ValidationReport::default();
+
+        for (index, address) in addresses.into_iter().enumerate() {
+            let result = self.validate_address(address.as_ref());
+
+            match result {
+                Ok(()) => report.valid += 1,
+                Err(QtumAddressError::InvalidChecksum) => report.bad_checksum += 1,
+                Err(QtumAddressError::NetworkMismatch { .. }) => report.wrong_network += 1,
+                Err(QtumAddressError::WrongAddressType { .. }) => report.wrong_type += 1,
+                Err(_) => report.malformed += 1,
+            }
+
+            if result.is_err() {
+                report.invalid_indices.push(index);
+            }
+            report.results.push(result);
+        }
+
+        report
+    }
+}
+
+/// Checks that a string is a valid base58 address, optionally of the given network.
+///
+/// Alphabet, length and checksum are always verified, the prefix only when a network is given.
+pub fn is_valid_base58_address(address: &str, network: Option<QtumNetwork>) -> bool {
+    match (decode_address_bytes(address, true), network) {
+        (Ok(payload), Some(network)) => payload[0] == network.to_prefix_byte(),
+        (Ok(_), None) => true,
+        (Err(_), _) => false,
+    }
+}
+
+/// Checks that a string is a 20-byte hex address, in any case and with an optional `0x` prefix
+pub fn is_valid_hex_address(address: &str) -> bool {
+    decode_hex(address).is_ok()
+}
+
+/// Canonical form of a hex address pasted from elsewhere: surrounding whitespace trimmed,
+/// `0x` prefix stripped and lowercased.
+///
+/// The result must be 40 hex digits. Whitespace or lookalike characters inside the address
+/// are still reported as `Whitespace` and `Homoglyph`.
+///
+/// ```rust
+/// use qtum_address_rust::*;
+///
+/// let hex = normalize_hex_address("  0x6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68\n").unwrap();
+/// assert_eq!(hex, "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68");
+/// ```
+pub fn normalize_hex_address(address: &str) -> Result<HexAddress, QtumAddressError> {
+    decode_hex(address).map(HexAddress::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::vec;
+
+    #[test]
+    fn normalize_pasted_hex() {
+        for input in &[
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+            "6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68",
+            "  0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68",
+            "0X6C89a1a6ca2ae7c00b248bb2832d6f480f27da68\t\r\n",
+        ] {
+            assert_eq!(
+                normalize_hex_address(input).unwrap(),
+                "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+            );
+        }
+
+        assert_eq!(
+            normalize_hex_address(" \n"),
+            Err(QtumAddressError::EmptyInput)
+        );
+        assert_eq!(
+            normalize_hex_address(" 0x6c89a1a6 "),
+            Err(QtumAddressError::InvalidLength {
+                expected: 40,
+                found: 8
+            })
+        );
+        assert_eq!(
+            normalize_hex_address("6c89a1a6ca2ae7c00b248bb2832d6f480f27dazz"),
+            Err(QtumAddressError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn trimmed_prefix_still_follows_the_builder() {
+        let qtum = QtumAddress::builder()
+            .network(QtumNetwork::Testnet)
+            .allow_0x_prefix(false)
+            .build();
+
+        assert_eq!(
+            qtum.fromhexaddress(" 0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"),
+            Err(QtumAddressError::InvalidHex)
+        );
+        assert_eq!(
+            qtum.fromhexaddress(" 6C89A1A6CA2AE7C00B248BB2832D6F480F27DA68 ")
+                .unwrap(),
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"
+        );
+    }
+
+    #[test]
+    fn validate_many_mixed_input() {
+        let mainnet = QtumAddress::new(QtumNetwork::Mainnet);
+        let testnet = QtumAddress::new(QtumNetwork::Testnet);
+
+        let mainnet_addr = mainnet
+            .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap()
+            .to_string();
+        let testnet_p2sh = QtumAddress::new_p2sh(QtumNetwork::Testnet)
+            .fromhexaddress("6c89a1a6ca2ae7c00b248bb2832d6f480f27da68")
+            .unwrap()
+            .to_string();
+
+        let addresses = vec![
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt".to_string(),
+            mainnet_addr,
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu".to_string(),
+            "not an address".to_string(),
+            String::new(),
+            "qQGqkA16ZY6bCYy7Qjr77eU4BPsdadibCG".to_string(),
+            testnet_p2sh,
+        ];
+
+        let report = testnet.validate_many(&addresses);
+
+        assert_eq!(report.total(), 7);
+        assert_eq!(report.valid, 2);
+        assert_eq!(report.wrong_network, 1);
+        assert_eq!(report.wrong_type, 1);
+        assert_eq!(report.bad_checksum, 1);
+        assert_eq!(report.malformed, 2);
+        assert_eq!(report.invalid_indices, vec![1, 2, 3, 4, 6]);
+        assert_eq!(report.results[4], Err(QtumAddressError::EmptyInput));
+        assert!(!report.all_valid());
+
+        let report = mainnet.validate_many(&addresses[1..2]);
+        assert!(report.all_valid());
+    }
+
+    #[test]
+    fn validate_address_checks_everything() {
+        let qtum = QtumAddress::new(QtumNetwork::Testnet);
+
+        assert_eq!(
+            qtum.validate_address("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Ok(())
+        );
+        assert_eq!(
+            qtum.validate_address("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu"),
+            Err(QtumAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            QtumAddress::new(QtumNetwork::Mainnet)
+                .validate_address("qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt"),
+            Err(QtumAddressError::NetworkMismatch {
+                expected: 0x3a,
+                found: 0x78
+            })
+        );
+    }
+
+    #[test]
+    fn is_valid_base58_address_near_misses() {
+        let addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+
+        assert!(is_valid_base58_address(addr, None));
+        assert!(is_valid_base58_address(addr, Some(QtumNetwork::Testnet)));
+        assert!(!is_valid_base58_address(addr, Some(QtumNetwork::Mainnet)));
+
+        assert!(!is_valid_base58_address(
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBu",
+            None
+        ));
+        assert!(!is_valid_base58_address(
+            "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrB",
+            None
+        ));
+        assert!(!is_valid_base58_address("", None));
+    }
+
+    #[test]
+    fn is_valid_hex_address_near_misses() {
+        assert!(is_valid_hex_address(
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        ));
+        assert!(is_valid_hex_address(
+            "0x6c89a1a6ca2ae7c00b248bb2832d6f480f27da68"
+        ));
+
+        assert!(!is_valid_hex_address(
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da6"
+        ));
+        assert!(!is_valid_hex_address(
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27da680"
+        ));
+        assert!(!is_valid_hex_address(
+            "6c89a1a6ca2ae7c00b248bb2832d6f480f27dag8"
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+//! `wasm-bindgen` entry points, enabled with the `wasm` feature
+//!
+//! Errors are thrown on the JavaScript side as strings carrying the `Display` message.
+use crate::{QtumAddress, QtumAddressError};
+use wasm_bindgen::prelude::*;
+
+/// Networks available from JavaScript, `wasm-bindgen` only exports enums without data
+/// so `QtumNetwork::Custom` is reachable through the `*_with_prefix` functions
+#[wasm_bindgen(js_name = QtumNetwork)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmNetwork {
+    /// Prefix address - 0x3a
+    Mainnet,
+    /// Prefix address - 0x78
+    Testnet,
+    /// Prefix address - 0x78
+    Regtest,
+}
+
+impl From<WasmNetwork> for crate::QtumNetwork {
+    fn from(network: WasmNetwork) -> Self {
+        match network {
+            WasmNetwork::Mainnet => crate::QtumNetwork::Mainnet,
+            WasmNetwork::Testnet => crate::QtumNetwork::Testnet,
+            WasmNetwork::Regtest => crate::QtumNetwork::Regtest,
+        }
+    }
+}
+
+/// Converts a base58 pubkeyhash address to a hex address for use in smart contracts.
+#[wasm_bindgen]
+pub fn gethexaddress(network: WasmNetwork, address: &str) -> Result<String, JsValue> {
+    QtumAddress::new(network.into())
+        .gethexaddress(address)
+        .map(String::from)
+        .map_err(to_js)
+}
+
+/// Converts a raw hex address to a base58 pubkeyhash address
+#[wasm_bindgen]
+pub fn fromhexaddress(network: WasmNetwork, address: &str) -> Result<String, JsValue> {
+    QtumAddress::new(network.into())
+        .fromhexaddress(address)
+        .map(String::from)
+        .map_err(to_js)
+}
+
+/// Same as `gethexaddress` for an arbitrary prefix byte
+#[wasm_bindgen(js_name = gethexaddressWithPrefix)]
+pub fn gethexaddress_with_prefix(prefix: u8, address: &str) -> Result<String, JsValue> {
+    QtumAddress::new(crate::QtumNetwork::Custom(prefix))
+        .gethexaddress(address)
+        .map(String::from)
+        .map_err(to_js)
+}
+
+/// Same as `fromhexaddress` for an arbitrary prefix byte
+#[wasm_bindgen(js_name = fromhexaddressWithPrefix)]
+pub fn fromhexaddress_with_prefix(prefix: u8, address: &str) -> Result<String, JsValue> {
+    QtumAddress::new(crate::QtumNetwork::Custom(prefix))
+        .fromhexaddress(address)
+        .map(String::from)
+        .map_err(to_js)
+}
+
+fn to_js(err: QtumAddressError) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
@@ -0,0 +1,32 @@
+// Smoke test for the wasm bindings.
+//
+//   wasm-pack build bindings --target nodejs --out-dir ../pkg -- --no-default-features --features wasm
+//   node tests/wasm/smoke.js
+const assert = require("assert");
+const qtum = require("../../pkg/qtum_address.js");
+
+const addr = "qTTH1Yr2eKCuDLqfxUyBLCAjmomQ8pyrBt";
+const hex = "6c89a1a6ca2ae7c00b248bb2832d6f480f27da68";
+
+assert.strictEqual(qtum.gethexaddress(qtum.QtumNetwork.Testnet, addr), hex);
+assert.strictEqual(qtum.fromhexaddress(qtum.QtumNetwork.Testnet, hex), addr);
+
+assert.throws(
+  () => qtum.gethexaddress(qtum.QtumNetwork.Mainnet, addr),
+  (err) => typeof err === "string" && err.startsWith("Invalid address")
+);
+
+// Named networks keep their parameters, P2SH addresses are rejected by P2PKH converters
+const p2sh = "MRCN1ENHQQzJiYv8EsmJF4FPdmApQFugvE";
+assert.throws(
+  () => qtum.gethexaddress(qtum.QtumNetwork.Mainnet, p2sh),
+  (err) => typeof err === "string" && err.includes("not a pubkeyhash address")
+);
+
+assert.strictEqual(qtum.gethexaddressWithPrefix(0x78, addr), hex);
+assert.strictEqual(
+  qtum.gethexaddressWithPrefix(0x1f, qtum.fromhexaddressWithPrefix(0x1f, hex)),
+  hex
+);
+
+console.log("wasm smoke test passed");